@@ -3,19 +3,22 @@ mod tests {
     use crate::error::ContractError;
     use crate::msg::{
         AllTokenLevelsResponse, CheckRoyaltiesResponse, Cw2981LevelingExecuteMsg,
-        Cw2981LevelingQueryMsg, InstantiateMsg, LevelingConfigResponse, RoyaltiesInfoResponse,
-        TokenLevelResponse,
+        Cw2981LevelingQueryMsg, InstantiateMsg, LevelingConfigResponse, LevelingNftExtension,
+        MigrateMsg, RoyaltiesInfoResponse, TokenLevelResponse,
     };
     use crate::query::{check_royalties, query_royalties_info};
     use crate::{entry, Cw2981LevelingContract};
 
-    use cosmwasm_std::{coins, from_json, to_json_binary, Addr, Empty, StdError, Uint128};
+    use cosmwasm_std::{coins, from_json, to_json_binary, Empty, Response, StdError, Uint128};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cw721::OwnerOfResponse;
-    use cw721_base::msg::{QueryMsg, SaleConfigResponse};
+    use cw721_base::msg::{QueryMsg, SaleConfigResponse, TokenWithInfo, TokensWithInfoResponse};
+    use cw721_base::pagination::OrderBy;
+    use cw721_base::state::DEFAULT_TIMELOCK_DELAY;
     use cw721_base::{ContractError as BaseContractError, ExecuteMsg};
     use cw_ownable::OwnershipError;
+    use cw_storage_plus::Map;
 
     const CREATOR: &str = "creator";
 
@@ -24,15 +27,14 @@ mod tests {
         let mut deps = mock_dependencies();
 
         let info = mock_info(CREATOR, &[]);
-        let token_uri = "token_uri";
-        let royalty_payment_address = Addr::unchecked("address");
+        let token_uri = "ipfs://token_uri";
         let royalty_percentage = 10;
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
             base_token_uri: token_uri.to_string(),
-            royalty_payment_address: royalty_payment_address.clone(),
-            royalty_percentage: royalty_percentage.clone(),
+            royalty_payment_address: "address".to_string(),
+            royalty_percentage,
             collection_size: 10,
             max_per_public: 5,
             max_per_allowlist: 1,
@@ -41,6 +43,28 @@ mod tests {
             allowlist_price: Uint128::from(1000000u64),
             og_price: Uint128::from(1000000u64),
         };
+
+        // addresses and base contract parameters are validated
+        let invalid_msg = InstantiateMsg {
+            royalty_payment_address: "".to_string(),
+            ..init_msg.clone()
+        };
+        let err =
+            entry::instantiate(deps.as_mut(), mock_env(), info.clone(), invalid_msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+        let invalid_msg = InstantiateMsg {
+            base_token_uri: "token_uri".to_string(),
+            ..init_msg.clone()
+        };
+        let err =
+            entry::instantiate(deps.as_mut(), mock_env(), info.clone(), invalid_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(cw721_base::ContractError::InvalidBaseTokenUri {
+                base_token_uri: "token_uri".to_string()
+            })
+        );
+
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         // check config
@@ -72,8 +96,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 101,
             collection_size: 10,
             max_per_public: 5,
@@ -97,8 +121,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 10,
             collection_size: 10,
             max_per_public: 5,
@@ -133,8 +157,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -164,6 +188,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 1,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info, mint_msg).unwrap();
 
@@ -209,8 +234,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -257,7 +282,7 @@ mod tests {
         let random_info = mock_info("random", &[]);
         let extension = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
             royalty_percentage: 8,
-            royalty_payment_address: Addr::unchecked("random"),
+            royalty_payment_address: "random".to_string(),
         };
         let exec_msg = ExecuteMsg::Extension { msg: extension };
         let err =
@@ -267,23 +292,93 @@ mod tests {
         // Update royalty info with error
         let extension = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
             royalty_percentage: 101,
-            royalty_payment_address: Addr::unchecked("address"),
+            royalty_payment_address: "address".to_string(),
         };
         let exec_msg = ExecuteMsg::Extension { msg: extension };
         let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
+        let extension = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+            royalty_percentage: 5,
+            royalty_payment_address: "".to_string(),
+        };
+        let exec_msg = ExecuteMsg::Extension { msg: extension };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+
+        // a lower percentage applies immediately
+        let extension = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+            royalty_percentage: 3,
+            royalty_payment_address: "address".to_string(),
+        };
+        let exec_msg = ExecuteMsg::Extension { msg: extension };
+        let res = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            [
+                ("action", "update_royalty_config"),
+                ("royalty_percentage", "3"),
+                ("royalty_payment_address", "address"),
+            ]
+        );
+        let query_res: RoyaltiesInfoResponse =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap()).unwrap();
+        assert_eq!(query_res.royalty_amount, Uint128::new(3));
 
-        // Update royalty info with new address and percentage
+        // Update royalty info with new address and a higher percentage
         let expected = RoyaltiesInfoResponse {
             address: "address_new".into(),
             royalty_amount: Uint128::new(5),
         };
         let extension = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
             royalty_percentage: 5,
-            royalty_payment_address: Addr::unchecked("address_new"),
+            royalty_payment_address: "address_new".to_string(),
         };
         let exec_msg = ExecuteMsg::Extension { msg: extension };
-        entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
+        let res = entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
+        let effective_at = mock_env().block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY);
+        assert_eq!(
+            res.attributes,
+            [
+                ("action", "update_royalty_config"),
+                ("royalty_percentage", "3"),
+                ("royalty_payment_address", "address"),
+                ("pending_royalty_percentage", "5"),
+                ("pending_royalty_payment_address", "address_new"),
+                ("change_id", "1"),
+                ("effective_at", &effective_at.seconds().to_string()),
+            ]
+        );
+
+        // both wait for the timelock
+        let query_res: RoyaltiesInfoResponse =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap()).unwrap();
+        assert_eq!(query_res.address, "address");
+        assert_eq!(query_res.royalty_amount, Uint128::new(3));
+
+        let apply_msg = ExecuteMsg::ApplyChange { id: 1 };
+        let err = entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            random_info.clone(),
+            apply_msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Base(BaseContractError::ChangeNotEffective { id: 1, .. })
+        ));
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY);
+        let res = entry::execute(deps.as_mut(), env, random_info, apply_msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            [
+                ("action", "apply_change"),
+                ("royalty_percentage", "5"),
+                ("royalty_payment_address", "address_new"),
+                ("change_id", "1")
+            ]
+        );
         let query_res: RoyaltiesInfoResponse =
             from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
         assert_eq!(query_res, expected);
@@ -296,8 +391,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -390,8 +485,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -407,6 +502,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 1,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
 
@@ -553,8 +649,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -570,6 +666,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 2,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
 
@@ -660,8 +757,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -677,6 +774,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 1,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
 
@@ -785,8 +883,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -802,6 +900,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 1,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
 
@@ -907,6 +1006,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn batch_moving_nfts() {
+        let mut deps = mock_dependencies();
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "PlayerX".to_string(),
+            symbol: "PX".to_string(),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
+            royalty_percentage: 4,
+            collection_size: 10,
+            max_per_public: 5,
+            max_per_allowlist: 1,
+            max_per_og: 1,
+            public_price: Uint128::from(1000000u64),
+            allowlist_price: Uint128::from(1000000u64),
+            og_price: Uint128::from(1000000u64),
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        // Mint tokens
+        let mint_msg = ExecuteMsg::MintTeam {
+            quantity: 4,
+            extension: Empty {},
+            transferable: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        // Turn on leveling
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Extension {
+                msg: Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
+                    leveling_open: true,
+                    max_experience: 100,
+                },
+            },
+        )
+        .unwrap();
+
+        // Toggle leveling on for every token
+        let env = mock_env();
+        for token_id in ["0", "1", "2", "3"] {
+            entry::execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::Extension {
+                    msg: Cw2981LevelingExecuteMsg::ToggleLeveling {
+                        token_id: token_id.to_string(),
+                    },
+                },
+            )
+            .unwrap();
+        }
+
+        // Batch transfer and batch send
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(20);
+        entry::execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap();
+        entry::execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::BatchSendNft {
+                contract: "game".to_string(),
+                token_ids: vec!["2".to_string(), "3".to_string()],
+                msg: to_json_binary("msg").unwrap(),
+                aggregate: Some(true),
+            },
+        )
+        .unwrap();
+
+        // Verify leveling was turned off for every moved token
+        for token_id in ["0", "1", "2", "3"] {
+            let query_res: TokenLevelResponse = from_json(
+                entry::query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Extension {
+                        msg: Cw2981LevelingQueryMsg::TokenLevel {
+                            token_id: token_id.to_string(),
+                        },
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                query_res,
+                TokenLevelResponse {
+                    leveling: false,
+                    leveling_start_timestamp: 0,
+                    total_exp: 20
+                }
+            );
+        }
+    }
+
     #[test]
     fn query_all_token_levels() {
         let mut deps = mock_dependencies();
@@ -914,8 +1123,8 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "PlayerX".to_string(),
             symbol: "PX".to_string(),
-            base_token_uri: "uri".to_string(),
-            royalty_payment_address: Addr::unchecked("address"),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
             royalty_percentage: 4,
             collection_size: 10,
             max_per_public: 5,
@@ -931,6 +1140,7 @@ mod tests {
         let mint_msg = ExecuteMsg::MintTeam {
             quantity: 5,
             extension: Empty {},
+            transferable: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
 
@@ -939,6 +1149,9 @@ mod tests {
             msg: Cw2981LevelingQueryMsg::AllTokenLevels {
                 start_after: None,
                 limit: None,
+                start_at: None,
+                end_before: None,
+                order: None,
             },
         };
         let query_res: AllTokenLevelsResponse =
@@ -1024,6 +1237,9 @@ mod tests {
             msg: Cw2981LevelingQueryMsg::AllTokenLevels {
                 start_after: None,
                 limit: Some(1),
+                start_at: None,
+                end_before: None,
+                order: None,
             },
         };
         let query_res: AllTokenLevelsResponse =
@@ -1038,6 +1254,9 @@ mod tests {
             msg: Cw2981LevelingQueryMsg::AllTokenLevels {
                 start_after: Some("0".to_string()),
                 limit: Some(5),
+                start_at: None,
+                end_before: None,
+                order: None,
             },
         };
         let query_res: AllTokenLevelsResponse =
@@ -1051,5 +1270,277 @@ mod tests {
                 ]
             }
         );
+
+        // levels are listed in numeric token order
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 5,
+                extension: Empty {},
+                transferable: None,
+            },
+        )
+        .unwrap();
+        entry::execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::Extension {
+                msg: Cw2981LevelingExecuteMsg::ToggleLeveling {
+                    token_id: "9".to_string(),
+                },
+            },
+        )
+        .unwrap();
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981LevelingQueryMsg::AllTokenLevels {
+                start_after: Some("1".to_string()),
+                limit: None,
+                start_at: None,
+                end_before: None,
+                order: None,
+            },
+        };
+        let query_res: AllTokenLevelsResponse =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            query_res,
+            AllTokenLevelsResponse {
+                token_levels: vec![
+                    ("2".to_string(), leveling_tokens.clone()),
+                    ("9".to_string(), leveling_tokens.clone())
+                ]
+            }
+        );
+
+        // descending, stopping before token 2
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981LevelingQueryMsg::AllTokenLevels {
+                start_after: None,
+                limit: None,
+                start_at: None,
+                end_before: Some("2".to_string()),
+                order: Some(OrderBy::Desc),
+            },
+        };
+        let query_res: AllTokenLevelsResponse =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            query_res,
+            AllTokenLevelsResponse {
+                token_levels: vec![("9".to_string(), leveling_tokens)]
+            }
+        );
+    }
+
+    #[test]
+    fn query_tokens_with_info() {
+        let mut deps = mock_dependencies();
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "PlayerX".to_string(),
+            symbol: "PX".to_string(),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
+            royalty_percentage: 4,
+            collection_size: 10,
+            max_per_public: 5,
+            max_per_allowlist: 1,
+            max_per_og: 1,
+            public_price: Uint128::from(1000000u64),
+            allowlist_price: Uint128::from(1000000u64),
+            og_price: Uint128::from(1000000u64),
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        // Mint tokens
+        let mint_msg = ExecuteMsg::MintTeam {
+            quantity: 2,
+            extension: Empty {},
+            transferable: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        // Grant experience to one of them
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Extension {
+                msg: Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
+                    leveling_open: true,
+                    max_experience: 100,
+                },
+            },
+        )
+        .unwrap();
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::Extension {
+                msg: Cw2981LevelingExecuteMsg::GrantBonusExperience {
+                    token_ids: vec!["1".to_string()],
+                    experience: 10,
+                },
+            },
+        )
+        .unwrap();
+
+        let expected = |token_id: &str, total_exp: u64| TokenWithInfo {
+            token_id: token_id.to_string(),
+            owner: CREATOR.to_string(),
+            token_uri: Some(format!("ipfs://uri/{}", token_id)),
+            extension: LevelingNftExtension {
+                transferable: true,
+                extension: Empty {},
+                token_level: TokenLevelResponse {
+                    leveling: false,
+                    leveling_start_timestamp: 0,
+                    total_exp,
+                },
+            },
+        };
+
+        let query_msg = QueryMsg::AllTokensWithInfo {
+            start_after: None,
+            limit: None,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let query_res: TokensWithInfoResponse<LevelingNftExtension> =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(query_res.tokens, vec![expected("0", 0), expected("1", 10)]);
+
+        let query_msg = QueryMsg::TokensWithInfo {
+            owner: CREATOR.to_string(),
+            start_after: Some("0".to_string()),
+            limit: None,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let query_res: TokensWithInfoResponse<LevelingNftExtension> =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(query_res.tokens, vec![expected("1", 10)]);
+    }
+
+    #[test]
+    fn upgrade_steps_are_ordered_and_released() {
+        let to: semver::Version = crate::CONTRACT_VERSION.parse().unwrap();
+        let versions: Vec<semver::Version> = crate::upgrades::STEPS
+            .iter()
+            .map(|(version, _)| version.parse().unwrap())
+            .collect();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(versions.iter().all(|version| *version <= to));
+    }
+
+    #[test]
+    fn migrate() {
+        let mut deps = mock_dependencies();
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "PlayerX".to_string(),
+            symbol: "PX".to_string(),
+            base_token_uri: "ipfs://uri".to_string(),
+            royalty_payment_address: "address".to_string(),
+            royalty_percentage: 4,
+            collection_size: 20,
+            max_per_public: 5,
+            max_per_allowlist: 1,
+            max_per_og: 1,
+            public_price: Uint128::from(1000000u64),
+            allowlist_price: Uint128::from(1000000u64),
+            og_price: Uint128::from(1000000u64),
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+        let mint_msg = ExecuteMsg::MintTeam {
+            quantity: 12,
+            extension: Empty {},
+            transferable: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info, mint_msg).unwrap();
+
+        // token levels saved before the numeric index existed
+        let legacy_levels: Map<&str, TokenLevelResponse> = Map::new("token_levels");
+        let token_level = TokenLevelResponse {
+            leveling: false,
+            leveling_start_timestamp: 0,
+            total_exp: 5,
+        };
+        for token_id in ["2", "10"] {
+            legacy_levels
+                .save(deps.as_mut().storage, token_id, &token_level)
+                .unwrap();
+        }
+
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw2981-leveling", "0.1.0")
+            .unwrap();
+        let res = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "migrate")
+                .add_attribute("from_version", "0.1.0")
+                .add_attribute("to_version", env!("CARGO_PKG_VERSION"))
+                .add_attribute("step", "0.2.0")
+                .add_attribute("reindexed_tokens", "12")
+                .add_attribute("num_holders", "1")
+                .add_attribute("step", "0.3.0")
+                .add_attribute("og_allocations", "0")
+                .add_attribute("allowlist_allocations", "0")
+                .add_attribute("leveling_step", "0.2.0")
+                .add_attribute("reindexed_token_levels", "2")
+        );
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981LevelingQueryMsg::AllTokenLevels {
+                start_after: None,
+                limit: None,
+                start_at: None,
+                end_before: None,
+                order: None,
+            },
+        };
+        let query_res: AllTokenLevelsResponse =
+            from_json(entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            query_res,
+            AllTokenLevelsResponse {
+                token_levels: vec![
+                    ("2".to_string(), token_level.clone()),
+                    ("10".to_string(), token_level)
+                ]
+            }
+        );
+
+        // only leveling contracts can be migrated, and never to an older version
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw721-base", "0.1.0").unwrap();
+        let err = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(BaseContractError::Version(
+                cw2::VersionError::WrongContract {
+                    expected: "crates.io:cw2981-leveling".to_string(),
+                    found: "crates.io:cw721-base".to_string(),
+                }
+            ))
+        );
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw2981-leveling", "9.0.0")
+            .unwrap();
+        let err = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(BaseContractError::CannotDowngrade {
+                from: "9.0.0".to_string(),
+                to: env!("CARGO_PKG_VERSION").to_string(),
+            })
+        );
     }
 }
@@ -1,35 +1,84 @@
 use crate::{
     error::ContractError,
-    msg::TokenLevelResponse,
+    msg::{Cw2981LevelingExecuteMsg, TokenLevelResponse},
     state::{LEVELING_CONFIG, ROYALTY_CONFIG, TOKEN_LEVELS},
     Cw2981LevelingContract,
 };
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Order, Response, StdResult};
 
 pub fn update_royalty_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     royalty_percentage: u64,
-    royalty_payment_address: Addr,
+    royalty_payment_address: String,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
     if royalty_percentage > 100 {
         return Err(ContractError::InvalidRoyaltyPercentage);
     }
+    let royalty_payment_address = deps.api.addr_validate(&royalty_payment_address)?;
 
+    // Lowering royalties only benefits buyers, so it applies right away
+    let mut royalty_config = ROYALTY_CONFIG.load(deps.storage)?;
+    if royalty_percentage <= royalty_config.royalty_percentage {
+        royalty_config.royalty_percentage = royalty_percentage;
+        ROYALTY_CONFIG.save(deps.storage, &royalty_config)?;
+    }
+
+    // Attributes show the config in effect, queued changes are listed apart
+    let mut res = Response::new()
+        .add_attribute("action", "update_royalty_config")
+        .add_attribute(
+            "royalty_percentage",
+            royalty_config.royalty_percentage.to_string(),
+        )
+        .add_attribute(
+            "royalty_payment_address",
+            royalty_config.royalty_payment_address.to_string(),
+        );
+    // Royalties are raised or redirected only once the timelock has passed
+    if royalty_percentage > royalty_config.royalty_percentage
+        || royalty_payment_address != royalty_config.royalty_payment_address
+    {
+        let msg = Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+            royalty_percentage,
+            royalty_payment_address: royalty_payment_address.to_string(),
+        };
+        let pending = Cw2981LevelingContract::default().queue_extension_change(
+            deps.storage,
+            &env.block,
+            &msg,
+        )?;
+        res = res
+            .add_attribute("pending_royalty_percentage", royalty_percentage.to_string())
+            .add_attribute("pending_royalty_payment_address", royalty_payment_address)
+            .add_attribute("change_id", pending.id.to_string())
+            .add_attribute("effective_at", pending.effective_at.seconds().to_string());
+    }
+    Ok(res)
+}
+
+// Applies a queued royalty config update, both the percentage and the payment
+// address as they were queued
+pub fn apply_royalty_config(
+    deps: DepsMut,
+    royalty_percentage: u64,
+    royalty_payment_address: String,
+) -> Result<Response, ContractError> {
+    let royalty_payment_address = deps.api.addr_validate(&royalty_payment_address)?;
     let mut royalty_config = ROYALTY_CONFIG.load(deps.storage)?;
     royalty_config.royalty_percentage = royalty_percentage;
     royalty_config.royalty_payment_address = royalty_payment_address.clone();
     ROYALTY_CONFIG.save(deps.storage, &royalty_config)?;
 
-    let res = Response::new()
-        .add_attribute("action", "update_royalty_config")
+    Ok(Response::new()
+        .add_attribute("action", "apply_change")
         .add_attribute("royalty_percentage", royalty_percentage.to_string())
         .add_attribute(
             "royalty_payment_address",
             royalty_payment_address.to_string(),
-        );
-    Ok(res)
+        ))
 }
 
 // Used to calculate the total experience based on the time elapsed
@@ -102,7 +151,7 @@ pub fn toggle_leveling(
 ) -> Result<Response, ContractError> {
     // Verify that this token is owned by the sender
     let contract = Cw2981LevelingContract::default();
-    let token_info = contract.tokens.load(deps.storage, &token_id)?;
+    let token_info = contract.load_token(deps.storage, &token_id)?;
     if token_info.owner != info.sender {
         return Err(ContractError::UnauthorizedLeveling {});
     }
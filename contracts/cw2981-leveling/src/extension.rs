@@ -0,0 +1,112 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
+use cw721_base::pagination::PageRange;
+use cw721_base::{ContractError as BaseContractError, Cw721Extension};
+
+use crate::error::ContractError;
+use crate::execute::{
+    apply_royalty_config, grant_bonus_experience, toggle_leveling, toggle_leveling_off,
+    update_leveling_config, update_royalty_config,
+};
+use crate::msg::{Cw2981LevelingExecuteMsg, Cw2981LevelingQueryMsg};
+use crate::query::{
+    check_royalties, query_all_token_levels, query_leveling_config, query_royalties_info,
+    query_royalty_config, query_token_level,
+};
+
+/// Royalty and leveling logic plugged into the base contract
+pub struct Leveling;
+
+impl Cw721Extension<Empty, Cw2981LevelingExecuteMsg, Cw2981LevelingQueryMsg> for Leveling {
+    type Error = ContractError;
+
+    fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw2981LevelingExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        match msg {
+            Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+                royalty_percentage,
+                royalty_payment_address,
+            } => {
+                update_royalty_config(deps, env, info, royalty_percentage, royalty_payment_address)
+            }
+            Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
+                leveling_open,
+                max_experience,
+            } => update_leveling_config(deps, env, info, leveling_open, max_experience),
+            Cw2981LevelingExecuteMsg::ToggleLeveling { token_id } => {
+                toggle_leveling(deps, env, info, token_id)
+            }
+            Cw2981LevelingExecuteMsg::GrantBonusExperience {
+                token_ids,
+                experience,
+            } => grant_bonus_experience(deps, info, token_ids, experience),
+        }
+    }
+
+    fn query(&self, deps: Deps, _env: Env, msg: Cw2981LevelingQueryMsg) -> StdResult<Binary> {
+        match msg {
+            Cw2981LevelingQueryMsg::RoyaltyConfig {} => {
+                to_json_binary(&query_royalty_config(deps)?)
+            }
+            Cw2981LevelingQueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => to_json_binary(&query_royalties_info(deps, token_id, sale_price)?),
+            Cw2981LevelingQueryMsg::CheckRoyalties {} => to_json_binary(&check_royalties(deps)?),
+            Cw2981LevelingQueryMsg::LevelingConfig {} => {
+                to_json_binary(&query_leveling_config(deps)?)
+            }
+            Cw2981LevelingQueryMsg::TokenLevel { token_id } => {
+                to_json_binary(&query_token_level(deps, token_id)?)
+            }
+            Cw2981LevelingQueryMsg::AllTokenLevels {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&query_all_token_levels(deps, range, limit)?)
+            }
+        }
+    }
+
+    /// Royalty payment address updates are the only changes leveling queues
+    fn apply_change(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        msg: Cw2981LevelingExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        match msg {
+            Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+                royalty_percentage,
+                royalty_payment_address,
+            } => apply_royalty_config(deps, royalty_percentage, royalty_payment_address),
+            _ => Err(BaseContractError::UnsupportedChange {}.into()),
+        }
+    }
+
+    /// Tokens stop leveling once they change hands
+    fn after_transfer(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        token_id: &str,
+        _recipient: &str,
+    ) -> Result<(), ContractError> {
+        toggle_leveling_off(deps, env.block.time.seconds(), token_id.to_string())
+    }
+}
@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw721_base::helpers::Cw721Contract as BaseHelper;
+use cw721_base::msg::TokensWithInfoResponse;
+use serde::de::DeserializeOwned;
+
+use crate::msg::{
+    AllTokenLevelsResponse, CheckRoyaltiesResponse, Cw2981LevelingExecuteMsg,
+    Cw2981LevelingQueryMsg, LevelingConfigResponse, LevelingNftExtension, RoyaltiesInfoResponse,
+    RoyaltyConfigResponse, TokenLevelResponse,
+};
+use crate::{ExecuteMsg, QueryMsg};
+
+/// Client for a deployed leveling contract, the base PlayerX messages are
+/// available through `base`
+#[cw_serde]
+pub struct Cw2981LevelingContract(pub Addr);
+
+#[allow(dead_code)]
+impl Cw2981LevelingContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn base(&self) -> BaseHelper<Cw2981LevelingQueryMsg, Cw2981LevelingExecuteMsg> {
+        BaseHelper(self.addr(), PhantomData, PhantomData)
+    }
+
+    pub fn call(&self, msg: Cw2981LevelingExecuteMsg) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&ExecuteMsg::Extension { msg })?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: Cw2981LevelingQueryMsg,
+    ) -> StdResult<T> {
+        let query = WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&QueryMsg::Extension { msg })?,
+        }
+        .into();
+        querier.query(&query)
+    }
+
+    /*** execute ***/
+
+    pub fn update_royalty_config(
+        &self,
+        royalty_percentage: u64,
+        royalty_payment_address: impl Into<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
+            royalty_percentage,
+            royalty_payment_address: royalty_payment_address.into(),
+        })
+    }
+
+    pub fn update_leveling_config(
+        &self,
+        leveling_open: bool,
+        max_experience: u64,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
+            leveling_open,
+            max_experience,
+        })
+    }
+
+    pub fn toggle_leveling(&self, token_id: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(Cw2981LevelingExecuteMsg::ToggleLeveling {
+            token_id: token_id.into(),
+        })
+    }
+
+    pub fn grant_bonus_experience(
+        &self,
+        token_ids: Vec<String>,
+        experience: u64,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw2981LevelingExecuteMsg::GrantBonusExperience {
+            token_ids,
+            experience,
+        })
+    }
+
+    /*** queries ***/
+
+    pub fn royalty_config(&self, querier: &QuerierWrapper) -> StdResult<RoyaltyConfigResponse> {
+        self.query(querier, Cw2981LevelingQueryMsg::RoyaltyConfig {})
+    }
+
+    pub fn royalty_info(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: impl Into<String>,
+        sale_price: Uint128,
+    ) -> StdResult<RoyaltiesInfoResponse> {
+        let req = Cw2981LevelingQueryMsg::RoyaltyInfo {
+            token_id: token_id.into(),
+            sale_price,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn check_royalties(&self, querier: &QuerierWrapper) -> StdResult<bool> {
+        let res: CheckRoyaltiesResponse =
+            self.query(querier, Cw2981LevelingQueryMsg::CheckRoyalties {})?;
+        Ok(res.royalty_payments)
+    }
+
+    pub fn leveling_config(&self, querier: &QuerierWrapper) -> StdResult<LevelingConfigResponse> {
+        self.query(querier, Cw2981LevelingQueryMsg::LevelingConfig {})
+    }
+
+    pub fn token_level(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: impl Into<String>,
+    ) -> StdResult<TokenLevelResponse> {
+        let req = Cw2981LevelingQueryMsg::TokenLevel {
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn all_token_levels(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(String, TokenLevelResponse)>> {
+        let req = Cw2981LevelingQueryMsg::AllTokenLevels {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: AllTokenLevelsResponse = self.query(querier, req)?;
+        Ok(res.token_levels)
+    }
+
+    /// Tokens of an owner with their leveling state merged in
+    pub fn tokens_with_info(
+        &self,
+        querier: &QuerierWrapper,
+        owner: impl Into<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<LevelingNftExtension>> {
+        self.base()
+            .tokens_with_info(querier, owner, start_after, limit)
+    }
+
+    /// All tokens with their leveling state merged in
+    pub fn all_tokens_with_info(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<LevelingNftExtension>> {
+        self.base()
+            .all_tokens_with_info(querier, start_after, limit)
+    }
+}
@@ -1,16 +1,20 @@
 pub mod contract_tests;
 pub mod error;
 pub mod execute;
+pub mod extension;
+pub mod helpers;
 pub mod msg;
+pub mod multitest_tests;
 pub mod query;
 pub mod state;
+pub mod upgrades;
 
 use cosmwasm_std::{to_json_binary, Empty};
 use cw721_base::{Cw721Contract, InstantiateMsg as BaseInstantiateMsg};
 
 use crate::error::ContractError;
-pub use crate::msg::InstantiateMsg;
 use crate::msg::{Cw2981LevelingExecuteMsg, Cw2981LevelingQueryMsg};
+pub use crate::msg::{InstantiateMsg, MigrateMsg};
 
 // Version info for migration
 const CONTRACT_NAME: &str = "crates.io:cw2981-leveling";
@@ -23,21 +27,16 @@ pub type QueryMsg = cw721_base::QueryMsg<Cw2981LevelingQueryMsg>;
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
-    use self::execute::{
-        grant_bonus_experience, toggle_leveling, toggle_leveling_off, update_leveling_config,
-        update_royalty_config,
-    };
+    use self::extension::Leveling;
     use self::msg::{LevelingConfigResponse, RoyaltyConfigResponse};
-    use self::query::{
-        check_royalties, query_all_token_levels, query_leveling_config, query_royalties_info,
-        query_royalty_config, query_token_level,
-    };
+    use self::query::{query_all_tokens_with_info, query_tokens_with_info};
     use self::state::{LEVELING_CONFIG, ROYALTY_CONFIG};
 
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
+    use cw721_base::pagination::PageRange;
 
     #[entry_point]
     pub fn instantiate(
@@ -55,7 +54,7 @@ pub mod entry {
 
         let royalty_config = RoyaltyConfigResponse {
             royalty_percentage: msg.royalty_percentage,
-            royalty_payment_address: msg.royalty_payment_address,
+            royalty_payment_address: deps.api.addr_validate(&msg.royalty_payment_address)?,
         };
         ROYALTY_CONFIG.save(deps.storage, &royalty_config)?;
 
@@ -83,98 +82,59 @@ pub mod entry {
 
     #[entry_point]
     pub fn execute(
-        mut deps: DepsMut,
+        deps: DepsMut,
         env: Env,
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
+        Cw2981LevelingContract::default().execute_with(&Leveling, deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
-            ExecuteMsg::Extension { msg } => match msg {
-                Cw2981LevelingExecuteMsg::UpdateRoyaltyConfig {
-                    royalty_percentage,
-                    royalty_payment_address,
-                } => update_royalty_config(deps, info, royalty_percentage, royalty_payment_address),
-                Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
-                    leveling_open,
-                    max_experience,
-                } => update_leveling_config(deps, env, info, leveling_open, max_experience),
-                Cw2981LevelingExecuteMsg::ToggleLeveling { token_id } => {
-                    toggle_leveling(deps, env, info, token_id)
-                }
-                Cw2981LevelingExecuteMsg::GrantBonusExperience {
-                    token_ids,
-                    experience,
-                } => grant_bonus_experience(deps, info, token_ids, experience),
-            },
-            ExecuteMsg::TransferNft {
-                recipient,
-                token_id,
+            QueryMsg::TokensWithInfo {
+                owner,
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
             } => {
-                let response = Cw2981LevelingContract::default().execute(
-                    deps.branch(),
-                    env.clone(),
-                    info.clone(),
-                    ExecuteMsg::TransferNft {
-                        recipient,
-                        token_id: token_id.clone(),
-                    },
-                );
-                if response.is_ok() {
-                    toggle_leveling_off(deps, env.block.time.seconds(), token_id.clone())?;
-                }
-                response.map_err(Into::into)
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&query_tokens_with_info(deps, owner, range, limit)?)
             }
-            ExecuteMsg::SendNft {
-                contract,
-                token_id,
-                msg: send_msg,
+            QueryMsg::AllTokensWithInfo {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
             } => {
-                let response = Cw2981LevelingContract::default().execute(
-                    deps.branch(),
-                    env.clone(),
-                    info.clone(),
-                    ExecuteMsg::SendNft {
-                        contract,
-                        token_id: token_id.clone(),
-                        msg: send_msg,
-                    },
-                );
-                if response.is_ok() {
-                    toggle_leveling_off(deps.branch(), env.block.time.seconds(), token_id.clone())?;
-                }
-                response.map_err(Into::into)
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&query_all_tokens_with_info(deps, range, limit)?)
             }
-            _ => Cw2981LevelingContract::default()
-                .execute(deps, env, info, msg)
-                .map_err(Into::into),
+            _ => Cw2981LevelingContract::default().query_with(&Leveling, deps, env, msg),
         }
     }
 
     #[entry_point]
-    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-        match msg {
-            QueryMsg::Extension { msg } => match msg {
-                Cw2981LevelingQueryMsg::RoyaltyConfig {} => {
-                    to_json_binary(&query_royalty_config(deps)?)
-                }
-                Cw2981LevelingQueryMsg::RoyaltyInfo {
-                    token_id,
-                    sale_price,
-                } => to_json_binary(&query_royalties_info(deps, token_id, sale_price)?),
-                Cw2981LevelingQueryMsg::CheckRoyalties {} => {
-                    to_json_binary(&check_royalties(deps)?)
-                }
-                Cw2981LevelingQueryMsg::LevelingConfig {} => {
-                    to_json_binary(&query_leveling_config(deps)?)
-                }
-                Cw2981LevelingQueryMsg::TokenLevel { token_id } => {
-                    to_json_binary(&query_token_level(deps, token_id)?)
-                }
-                Cw2981LevelingQueryMsg::AllTokenLevels { start_after, limit } => {
-                    to_json_binary(&query_all_token_levels(deps, start_after, limit)?)
-                }
-            },
-            _ => Cw2981LevelingContract::default().query(deps, env, msg),
-        }
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        Ok(Cw2981LevelingContract::default().reply(deps, env, msg)?)
+    }
+
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+        upgrades::migrate(deps)
     }
 }
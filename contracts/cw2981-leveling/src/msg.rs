@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CustomMsg, Uint128};
+use cosmwasm_std::{Addr, CustomMsg, Empty, Uint128};
+use cw721_base::pagination::OrderBy;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -13,7 +14,7 @@ pub struct InstantiateMsg {
     pub collection_size: u64,
     // Royalty info
     pub royalty_percentage: u64,
-    pub royalty_payment_address: Addr,
+    pub royalty_payment_address: String,
     // Sale config
     pub og_price: Uint128,
     pub allowlist_price: Uint128,
@@ -23,12 +24,17 @@ pub struct InstantiateMsg {
     pub max_per_public: u64,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum Cw2981LevelingExecuteMsg {
-    /// Updates royalty info for the contract
+    /// Updates royalty info for the contract. Lowering the percentage applies
+    /// right away, raising it or changing the payment address is queued as a
+    /// timelocked change that applies both as sent.
     UpdateRoyaltyConfig {
         royalty_percentage: u64,
-        royalty_payment_address: Addr,
+        royalty_payment_address: String,
     },
     /// Update leveling config for the contract
     UpdateLevelingConfig {
@@ -83,6 +89,12 @@ pub enum Cw2981LevelingQueryMsg {
     AllTokenLevels {
         start_after: Option<String>,
         limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
     },
 }
 
@@ -128,6 +140,15 @@ pub struct TokenLevelResponse {
     pub total_exp: u64,
 }
 
+/// Token extension returned by `TokensWithInfo` and `AllTokensWithInfo`,
+/// with the leveling state of the token merged in
+#[cw_serde]
+pub struct LevelingNftExtension {
+    pub transferable: bool,
+    pub extension: Empty,
+    pub token_level: TokenLevelResponse,
+}
+
 #[cw_serde]
 pub struct AllTokenLevelsResponse {
     pub token_levels: Vec<(String, TokenLevelResponse)>,
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::entry;
+    use crate::msg::{
+        Cw2981LevelingExecuteMsg, Cw2981LevelingQueryMsg, InstantiateMsg, RoyaltiesInfoResponse,
+        TokenLevelResponse,
+    };
+    use crate::{ExecuteMsg, QueryMsg};
+
+    use cosmwasm_std::{Addr, Empty, Uint128};
+    use cw721_base::multitest::{Phase, Suite, COLLECTION_SIZE, OWNER, PRICE};
+    use cw_multi_test::error::AnyResult;
+    use cw_multi_test::{AppResponse, Contract, ContractWrapper};
+    use serde::de::DeserializeOwned;
+
+    const MAX_EXPERIENCE: u64 = 1_000;
+
+    fn leveling_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(entry::execute, entry::instantiate, entry::query)
+            .with_reply(entry::reply)
+            .with_migrate(entry::migrate);
+        Box::new(contract)
+    }
+
+    fn setup_suite() -> Suite<Empty> {
+        let msg = InstantiateMsg {
+            name: "PlayerX".to_string(),
+            symbol: "PX".to_string(),
+            base_token_uri: "ipfs://playerx".to_string(),
+            collection_size: COLLECTION_SIZE,
+            royalty_percentage: 5,
+            royalty_payment_address: OWNER.to_string(),
+            og_price: Uint128::new(PRICE),
+            allowlist_price: Uint128::new(PRICE),
+            public_price: Uint128::new(PRICE),
+            max_per_og: 1,
+            max_per_allowlist: 2,
+            max_per_public: 5,
+        };
+        let mut suite = Suite::with_contract(leveling_contract(), &msg);
+        let owner = suite.owner.clone();
+        execute_leveling(
+            &mut suite,
+            &owner,
+            Cw2981LevelingExecuteMsg::UpdateLevelingConfig {
+                leveling_open: true,
+                max_experience: MAX_EXPERIENCE,
+            },
+        )
+        .unwrap();
+        suite
+    }
+
+    fn execute_leveling(
+        suite: &mut Suite<Empty>,
+        sender: &Addr,
+        msg: Cw2981LevelingExecuteMsg,
+    ) -> AnyResult<AppResponse> {
+        suite.execute(sender, &ExecuteMsg::Extension { msg }, &[])
+    }
+
+    fn query_leveling<R: DeserializeOwned>(suite: &Suite<Empty>, msg: Cw2981LevelingQueryMsg) -> R {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(suite.collection.clone(), &QueryMsg::Extension { msg })
+            .unwrap()
+    }
+
+    fn token_level(suite: &Suite<Empty>, token_id: &str) -> TokenLevelResponse {
+        query_leveling(
+            suite,
+            Cw2981LevelingQueryMsg::TokenLevel {
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
+    fn toggle_leveling(suite: &mut Suite<Empty>, sender: &Addr, token_id: &str) {
+        let msg = Cw2981LevelingExecuteMsg::ToggleLeveling {
+            token_id: token_id.to_string(),
+        };
+        execute_leveling(suite, sender, msg).unwrap();
+    }
+
+    #[test]
+    fn leveling_accrues_over_time() {
+        let mut suite = setup_suite();
+        let alice = suite.user(0);
+        suite.set_phase(Phase::Public).unwrap();
+        suite.mint(&alice, Phase::Public, 2).unwrap();
+        assert_eq!(suite.nft_balance(&alice), 2);
+
+        toggle_leveling(&mut suite, &alice, "0");
+        suite.advance_seconds(300);
+        toggle_leveling(&mut suite, &alice, "0");
+        let level = token_level(&suite, "0");
+        assert!(!level.leveling);
+        assert_eq!(level.total_exp, 300);
+
+        // experience is capped at the configured maximum
+        toggle_leveling(&mut suite, &alice, "0");
+        suite.advance_blocks(1_000);
+        toggle_leveling(&mut suite, &alice, "0");
+        assert_eq!(token_level(&suite, "0").total_exp, MAX_EXPERIENCE);
+        assert_eq!(token_level(&suite, "1").total_exp, 0);
+    }
+
+    #[test]
+    fn transfer_stops_leveling() {
+        let mut suite = setup_suite();
+        let (alice, bob) = (suite.user(0), suite.user(1));
+        suite.set_phase(Phase::Public).unwrap();
+        suite.mint(&alice, Phase::Public, 1).unwrap();
+
+        toggle_leveling(&mut suite, &alice, "0");
+        suite.advance_seconds(120);
+        suite.transfer(&alice, &bob, "0").unwrap();
+
+        let level = token_level(&suite, "0");
+        assert!(!level.leveling);
+        assert_eq!(level.total_exp, 120);
+        assert_eq!(suite.owner_of("0"), bob.to_string());
+        assert_eq!(suite.nft_balance(&alice), 0);
+
+        // only the new owner can level the token
+        let msg = Cw2981LevelingExecuteMsg::ToggleLeveling {
+            token_id: "0".to_string(),
+        };
+        execute_leveling(&mut suite, &alice, msg).unwrap_err();
+        toggle_leveling(&mut suite, &bob, "0");
+        assert!(token_level(&suite, "0").leveling);
+    }
+
+    #[test]
+    fn mint_payments_and_royalties() {
+        let mut suite = setup_suite();
+        let (alice, carol) = (suite.user(0), suite.user(2));
+        suite.add_to_allowlist(&[(&alice, 2)]).unwrap();
+        suite.set_phase(Phase::Allowlist).unwrap();
+        suite.mint(&alice, Phase::Allowlist, 2).unwrap();
+
+        let collection = suite.collection.clone();
+        assert_eq!(suite.bank_balance(&collection), 2 * PRICE);
+        suite.set_withdraw_address(&carol).unwrap();
+        suite.apply_pending_changes().unwrap();
+        let before = suite.bank_balance(&carol);
+        suite.withdraw_funds(2 * PRICE).unwrap();
+        assert_eq!(suite.bank_balance(&carol), before + 2 * PRICE);
+
+        let royalties: RoyaltiesInfoResponse = query_leveling(
+            &suite,
+            Cw2981LevelingQueryMsg::RoyaltyInfo {
+                token_id: "1".to_string(),
+                sale_price: Uint128::new(PRICE),
+            },
+        );
+        assert_eq!(royalties.address, OWNER);
+        assert_eq!(royalties.royalty_amount, Uint128::new(PRICE * 5 / 100));
+    }
+}
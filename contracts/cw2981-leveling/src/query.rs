@@ -1,11 +1,13 @@
 use crate::msg::{
-    AllTokenLevelsResponse, CheckRoyaltiesResponse, LevelingConfigResponse, RoyaltiesInfoResponse,
-    RoyaltyConfigResponse, TokenLevelResponse,
+    AllTokenLevelsResponse, CheckRoyaltiesResponse, LevelingConfigResponse, LevelingNftExtension,
+    RoyaltiesInfoResponse, RoyaltyConfigResponse, TokenLevelResponse,
 };
 use crate::state::{LEVELING_CONFIG, ROYALTY_CONFIG, TOKEN_LEVELS};
 use crate::Cw2981LevelingContract;
-use cosmwasm_std::{Decimal, Deps, Order, StdResult, Uint128};
-use cw_storage_plus::Bound;
+use cosmwasm_std::{Decimal, Deps, Empty, StdResult, Uint128};
+use cw721_base::msg::{NftInfoExtension, TokenWithInfo, TokensWithInfoResponse};
+use cw721_base::pagination::PageRange;
+use cw721_base::state::numeric_key;
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 1000;
@@ -27,7 +29,7 @@ pub fn query_royalties_info(
 ) -> StdResult<RoyaltiesInfoResponse> {
     let contract = Cw2981LevelingContract::default();
     // Will cause query to fail if token_id doesn't exist
-    let _token_info = contract.tokens.load(deps.storage, &token_id)?;
+    let _token_info = contract.load_token(deps.storage, &token_id)?;
 
     let royalty_config = ROYALTY_CONFIG.load(deps.storage)?;
     let royalty_percentage = Decimal::percent(royalty_config.royalty_percentage);
@@ -70,14 +72,16 @@ pub fn query_token_level(deps: Deps, token_id: String) -> StdResult<TokenLevelRe
 
 pub fn query_all_token_levels(
     deps: Deps,
-    start_after: Option<String>,
+    range: PageRange,
     limit: Option<u32>,
 ) -> StdResult<AllTokenLevelsResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start_bound = start_after.as_deref().map(Bound::exclusive);
+    let (min, max, order) = range.bounds(numeric_key)?;
 
     let token_levels: StdResult<Vec<(String, TokenLevelResponse)>> = TOKEN_LEVELS
-        .range(deps.storage, start_bound, None, Order::Ascending)
+        .idx
+        .number
+        .range(deps.storage, min, max, order)
         .take(limit)
         .map(|item| {
             let (key, token_level) = item?;
@@ -89,3 +93,49 @@ pub fn query_all_token_levels(
         token_levels: token_levels?,
     })
 }
+
+pub fn query_tokens_with_info(
+    deps: Deps,
+    owner: String,
+    range: PageRange,
+    limit: Option<u32>,
+) -> StdResult<TokensWithInfoResponse<LevelingNftExtension>> {
+    let contract = Cw2981LevelingContract::default();
+    let res = contract.tokens_with_info(deps, owner, range, limit)?;
+    merge_token_levels(deps, res)
+}
+
+pub fn query_all_tokens_with_info(
+    deps: Deps,
+    range: PageRange,
+    limit: Option<u32>,
+) -> StdResult<TokensWithInfoResponse<LevelingNftExtension>> {
+    let contract = Cw2981LevelingContract::default();
+    let res = contract.all_tokens_with_info(deps, range, limit)?;
+    merge_token_levels(deps, res)
+}
+
+fn merge_token_levels(
+    deps: Deps,
+    res: TokensWithInfoResponse<NftInfoExtension<Empty>>,
+) -> StdResult<TokensWithInfoResponse<LevelingNftExtension>> {
+    let tokens: StdResult<Vec<_>> = res
+        .tokens
+        .into_iter()
+        .map(|token| {
+            let token_level = query_token_level(deps, token.token_id.clone())?;
+            Ok(TokenWithInfo {
+                token_id: token.token_id,
+                owner: token.owner,
+                token_uri: token.token_uri,
+                extension: LevelingNftExtension {
+                    transferable: token.extension.transferable,
+                    extension: token.extension.extension,
+                    token_level,
+                },
+            })
+        })
+        .collect();
+
+    Ok(TokensWithInfoResponse { tokens: tokens? })
+}
@@ -1,6 +1,32 @@
 use crate::msg::{LevelingConfigResponse, RoyaltyConfigResponse, TokenLevelResponse};
-use cw_storage_plus::{Item, Map};
+use cw721_base::state::token_number;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
 
 pub const ROYALTY_CONFIG: Item<RoyaltyConfigResponse> = Item::new("royalty_config");
 pub const LEVELING_CONFIG: Item<LevelingConfigResponse> = Item::new("leveling_config");
-pub const TOKEN_LEVELS: Map<&str, TokenLevelResponse> = Map::new("token_levels");
+pub const TOKEN_LEVELS: IndexedMap<&str, TokenLevelResponse, TokenLevelIndexes> = IndexedMap::new(
+    "token_levels",
+    TokenLevelIndexes {
+        number: MultiIndex::new(
+            token_level_number_idx,
+            "token_levels",
+            "token_levels__number",
+        ),
+    },
+);
+
+pub struct TokenLevelIndexes<'a> {
+    /// Orders token levels by the numeric value of their token id
+    pub number: MultiIndex<'a, u64, TokenLevelResponse, String>,
+}
+
+impl<'a> IndexList<TokenLevelResponse> for TokenLevelIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenLevelResponse>> + '_> {
+        let v: Vec<&dyn Index<TokenLevelResponse>> = vec![&self.number];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn token_level_number_idx(pk: &[u8], _d: &TokenLevelResponse) -> u64 {
+    token_number(pk)
+}
@@ -0,0 +1,63 @@
+use cosmwasm_std::{DepsMut, Empty, Order, Response, StdResult};
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::msg::{Cw2981LevelingExecuteMsg, Cw2981LevelingQueryMsg};
+use crate::state::{LEVELING_CONFIG, ROYALTY_CONFIG, TOKEN_LEVELS};
+use crate::{CONTRACT_NAME, CONTRACT_VERSION};
+
+type Step = fn(DepsMut) -> Result<Response, ContractError>;
+
+/// Leveling storage steps in version order, run before the base contract steps.
+/// Like the base steps, they never get ahead of the workspace version.
+pub(crate) const STEPS: [(&str, Step); 1] = [("0.2.0", v0_2)];
+
+/// Runs the leveling storage steps since the stored version, then the base
+/// contract upgrade steps, which also store the new contract version. Leveling
+/// steps are reported as `leveling_step` attributes after the base steps.
+pub fn migrate(mut deps: DepsMut) -> Result<Response, ContractError> {
+    let to: Version = CONTRACT_VERSION
+        .parse()
+        .map_err(cw721_base::ContractError::from)?;
+    let from = cw721_base::upgrades::assert_can_migrate(deps.storage, CONTRACT_NAME, &to)?;
+
+    let mut leveling_res = Response::new();
+    for (version, step) in STEPS {
+        let version: Version = version.parse().map_err(cw721_base::ContractError::from)?;
+        if from < version && version <= to {
+            let step_res = step(deps.branch())?;
+            leveling_res = leveling_res
+                .add_attribute("leveling_step", version.to_string())
+                .add_attributes(step_res.attributes)
+                .add_submessages(step_res.messages)
+                .add_events(step_res.events);
+        }
+    }
+
+    let res = cw721_base::upgrades::migrate::<
+        Empty,
+        Empty,
+        Cw2981LevelingExecuteMsg,
+        Cw2981LevelingQueryMsg,
+    >(deps, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(res
+        .add_attributes(leveling_res.attributes)
+        .add_submessages(leveling_res.messages)
+        .add_events(leveling_res.events))
+}
+
+/// Re-saves every token level so the numeric index is populated for levels
+/// stored before it existed, and checks both configs still load.
+fn v0_2(deps: DepsMut) -> Result<Response, ContractError> {
+    ROYALTY_CONFIG.load(deps.storage)?;
+    LEVELING_CONFIG.load(deps.storage)?;
+
+    let token_levels = TOKEN_LEVELS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, token_level) in &token_levels {
+        TOKEN_LEVELS.save(deps.storage, token_id, token_level)?;
+    }
+
+    Ok(Response::new().add_attribute("reindexed_token_levels", token_levels.len().to_string()))
+}
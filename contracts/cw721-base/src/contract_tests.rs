@@ -2,24 +2,40 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    coin, coins, from_json, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Empty,
-    Response, StdError, Uint128, WasmMsg,
+    attr, coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgResult, Uint128, WasmMsg,
 };
 
 use cw721::{
     AllNftInfoResponse, Approval, ApprovalResponse, ContractInfoResponse, Cw721Query,
     Cw721ReceiveMsg, Expiration, NftInfoResponse, OperatorResponse, OperatorsResponse,
-    OwnerOfResponse,
+    OwnerOfResponse, TokensResponse,
 };
 use cw_ownable::OwnershipError;
-
-use crate::msg::{AdminResponse, BaseTokenUriResponse, CollectionSizeResponse, SaleConfigResponse};
-use crate::{ContractError, Cw721Contract, ExecuteMsg, Extension, InstantiateMsg, QueryMsg};
+use cw_storage_plus::{Item, Map};
+
+use crate::hook::HOOK_REPLY_ID;
+use crate::msg::{
+    AdminResponse, Allocation, AllocationResponse, AllocationsResponse, BalanceResponse,
+    BaseTokenUriResponse, CollectionSizeResponse, Holder, HoldersResponse, HookErrorPolicy,
+    HookResponse, HooksResponse, NftInfoExtension, NumHoldersResponse, OperatorFilterMode,
+    OperatorFilterResponse, OperatorRegistryResponse, PendingChange, PendingChangesResponse,
+    ReservedSupplyResponse, SaleConfigResponse, TimelockDelayResponse, TimelockedChange,
+    TokenWithInfo, TokensWithInfoResponse, UpstreamMigrateMsg,
+};
+use crate::pagination::{OrderBy, PageRange};
+use crate::state::{TokenInfo, DEFAULT_TIMELOCK_DELAY};
+use crate::{
+    entry, ContractError, Cw721BatchReceiveMsg, Cw721Contract, Cw721Extension, Cw721HookMsg,
+    ExecuteMsg, Extension, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use cw721_base_018 as v18;
 
 const ADMIN: &str = "creator";
 const CONTRACT_NAME: &str = "PlayerX";
 const SYMBOL: &str = "PX";
-const BASE_TOKEN_URI: &str = "base_token_uri";
+const BASE_TOKEN_URI: &str = "ipfs://base_token_uri";
 const COLLECTION_SIZE: u64 = 100;
 const MAX_PER_PUBLIC: u64 = 5;
 const MAX_PER_ALLOWLIST: u64 = 1;
@@ -28,6 +44,13 @@ const PUBLIC_PRICE: u64 = 100000;
 const ALLOWLIST_PRICE: u64 = 100000;
 const OG_PRICE: u64 = 100000;
 
+/// Env of a block where changes queued at `mock_env` can be applied
+fn after_timelock() -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY);
+    env
+}
+
 fn setup_contract(deps: DepsMut<'_>) -> Cw721Contract<'static, Extension, Empty, Empty, Empty> {
     let contract = Cw721Contract::default();
     let msg = InstantiateMsg {
@@ -99,6 +122,128 @@ fn proper_instantiation() {
     assert_eq!(0, tokens.tokens.len());
 }
 
+#[test]
+fn config_validation() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty, Empty, Empty>::default();
+    let info = mock_info(ADMIN, &[]);
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        base_token_uri: BASE_TOKEN_URI.to_string(),
+        collection_size: COLLECTION_SIZE,
+        max_per_public: MAX_PER_PUBLIC,
+        max_per_allowlist: MAX_PER_ALLOWLIST,
+        max_per_og: MAX_PER_OG,
+        public_price: Uint128::from(PUBLIC_PRICE),
+        allowlist_price: Uint128::from(ALLOWLIST_PRICE),
+        og_price: Uint128::from(OG_PRICE),
+    };
+
+    let invalid = InstantiateMsg {
+        collection_size: 0,
+        ..msg.clone()
+    };
+    let err = contract
+        .instantiate(deps.as_mut(), mock_env(), info.clone(), invalid)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCollectionSize {});
+
+    let invalid = InstantiateMsg {
+        max_per_allowlist: 0,
+        ..msg.clone()
+    };
+    let err = contract
+        .instantiate(deps.as_mut(), mock_env(), info.clone(), invalid)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ZeroMintLimit {
+            phase: "allowlist".to_string()
+        }
+    );
+
+    // uris need a known scheme and a path that token ids can be appended to
+    for base_token_uri in [
+        "",
+        "base_token_uri",
+        "ipfs://",
+        "https://x.io/",
+        "ar://a b",
+        "http://x.io",
+    ] {
+        let invalid = InstantiateMsg {
+            base_token_uri: base_token_uri.to_string(),
+            ..msg.clone()
+        };
+        let err = contract
+            .instantiate(deps.as_mut(), mock_env(), info.clone(), invalid)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidBaseTokenUri {
+                base_token_uri: base_token_uri.to_string()
+            }
+        );
+    }
+
+    // updates are validated the same way
+    contract
+        .instantiate(deps.as_mut(), mock_env(), info.clone(), msg)
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetCollectionSize { collection_size: 0 },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCollectionSize {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetBaseTokenUri {
+                base_token_uri: "ipfs://".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBaseTokenUri { .. }));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetSaleConfig {
+                og_price: Uint128::zero(),
+                allowlist_price: Uint128::zero(),
+                public_price: Uint128::zero(),
+                max_per_og: 1,
+                max_per_allowlist: 1,
+                max_per_public: 0,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ZeroMintLimit {
+            phase: "public".to_string()
+        }
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetBaseTokenUri {
+                base_token_uri: "ar://collection".to_string(),
+            },
+        )
+        .unwrap();
+}
+
 #[test]
 fn update_sale_config() {
     let mut deps = mock_dependencies();
@@ -165,6 +310,78 @@ fn update_sale_config() {
     assert_eq!(expected, sale_config);
 }
 
+#[test]
+fn partial_sale_config_update() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let admin = mock_info(ADMIN, &[]);
+
+    let update =
+        |public_price: Option<u64>, max_per_og: Option<u64>| ExecuteMsg::UpdateSaleConfig {
+            og_price: None,
+            allowlist_price: Some(Uint128::from(ALLOWLIST_PRICE)),
+            public_price: public_price.map(Uint128::from),
+            max_per_og,
+            max_per_allowlist: None,
+            max_per_public: None,
+        };
+
+    // Only the owner can update
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            update(Some(1), None),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // Unchanged and missing fields are left out of the attributes
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            update(Some(1), None),
+        )
+        .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_sale_config"),
+            attr("public_price_before", PUBLIC_PRICE.to_string()),
+            attr("public_price_after", "1"),
+        ]
+    );
+    let sale_config = contract.sale_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(sale_config.public_price, Uint128::new(1));
+    assert_eq!(sale_config.og_price, Uint128::from(OG_PRICE));
+    assert_eq!(sale_config.max_per_public, MAX_PER_PUBLIC);
+
+    // Limits must stay positive, a failed update changes nothing
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            update(None, Some(2)),
+        )
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), admin, update(Some(5), Some(0)))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ZeroMintLimit {
+            phase: "og".to_string()
+        }
+    );
+    let sale_config = contract.sale_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(sale_config.public_price, Uint128::new(1));
+    assert_eq!(sale_config.max_per_og, 2);
+}
+
 #[test]
 fn update_sale_state() {
     let mut deps = mock_dependencies();
@@ -304,7 +521,7 @@ fn update_base_token_uri() {
     assert_eq!(expected, base_token_uri);
 
     // Random can't update
-    let new_base_token_uri = "new_base_token_uri";
+    let new_base_token_uri = "ipfs://new_base_token_uri";
     let msg = ExecuteMsg::SetBaseTokenUri {
         base_token_uri: new_base_token_uri.to_string(),
     };
@@ -354,6 +571,7 @@ fn update_collection_size() {
     let mint_msg = ExecuteMsg::MintTeam {
         quantity: 10,
         extension: None,
+        transferable: None,
     };
     let admin = mock_info(ADMIN, &[]);
     let _ = contract
@@ -411,178 +629,467 @@ fn update_collection_size() {
 }
 
 #[test]
-fn mint_team() {
+fn reserved_supply() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let admin = mock_info(ADMIN, &[]);
 
-    let mint_msg = ExecuteMsg::MintTeam {
-        quantity: 10,
-        extension: None,
-    };
-
-    // random cannot mint
-    let random = mock_info("random", &[]);
+    // Random can't reserve
     let err = contract
-        .execute(deps.as_mut(), mock_env(), random, mint_msg.clone())
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            ExecuteMsg::SetReservedSupply { reserved_supply: 1 },
+        )
         .unwrap_err();
     assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
 
-    // can't mint 0
-    let allowed = mock_info(ADMIN, &[]);
+    // Reservation must fit in the collection
     let err = contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            allowed.clone(),
-            ExecuteMsg::MintTeam {
-                quantity: 0,
-                extension: None,
+            admin.clone(),
+            ExecuteMsg::SetReservedSupply {
+                reserved_supply: COLLECTION_SIZE + 1,
             },
         )
         .unwrap_err();
-    assert_eq!(err, ContractError::InvalidQuantity {});
-
-    // admin can mint
-    let allowed = mock_info(ADMIN, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), mock_env(), allowed.clone(), mint_msg)
-        .unwrap();
-
-    // ensure num tokens increases
-    let count = contract.num_tokens(deps.as_ref()).unwrap();
-    assert_eq!(10, count.count);
+    assert_eq!(err, ContractError::InvalidReservedSupply {});
 
-    // ensure nft info is correct
-    let info = contract.nft_info(deps.as_ref(), "0".to_string()).unwrap();
-    let expected_token_uri = format!("{}/{}", BASE_TOKEN_URI, "0");
-    assert_eq!(
-        info,
-        NftInfoResponse::<Extension> {
-            token_uri: Some(expected_token_uri),
-            extension: None,
-        }
-    );
-
-    let all_info: AllNftInfoResponse<Option<Empty>> = contract
-        .all_nft_info(deps.as_ref(), mock_env(), "8".to_string(), true)
-        .unwrap();
-    let expected_token_uri = format!("{}/{}", BASE_TOKEN_URI, "8");
-    assert_eq!(
-        all_info,
-        AllNftInfoResponse::<Extension> {
-            access: OwnerOfResponse {
-                owner: ADMIN.to_string(),
-                approvals: vec![],
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetReservedSupply {
+                reserved_supply: COLLECTION_SIZE - 3,
             },
-            info: NftInfoResponse {
-                token_uri: Some(expected_token_uri),
-                extension: None,
-            }
-        }
-    );
-
-    // ensure owner info is correct
-    let owner = contract
-        .owner_of(deps.as_ref(), mock_env(), "0".to_string(), true)
+        )
         .unwrap();
-    assert_eq!(
-        owner,
-        OwnerOfResponse {
-            owner: ADMIN.to_string(),
-            approvals: vec![],
-        }
-    );
-
-    // can't mint past collection size
-    let mint_msg = ExecuteMsg::MintTeam {
-        quantity: 91,
-        extension: None,
-    };
-    let err = contract
-        .execute(deps.as_mut(), mock_env(), allowed, mint_msg)
-        .unwrap_err();
-    assert_eq!(err, ContractError::MaxSupplyReached {});
-}
-
-#[test]
-fn mint_og() {
-    let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
-
-    // Open og minting
-    let admin = mock_info(ADMIN, &[]);
     contract
         .execute(
             deps.as_mut(),
             mock_env(),
             admin.clone(),
-            ExecuteMsg::SetOgSale { open: true },
+            ExecuteMsg::SetPublicSale { open: true },
         )
         .unwrap();
 
-    // random can't mint if not og
-    let random = mock_info("random", &[]);
-    let err = contract
+    // Public mints can't consume the reservation
+    let buyer = mock_info("buyer", &coins(3 * PUBLIC_PRICE as u128, "usei"));
+    contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            random.clone(),
-            ExecuteMsg::MintOg {
-                quantity: 1,
+            buyer,
+            ExecuteMsg::MintPublic {
+                quantity: 3,
                 extension: None,
             },
         )
-        .unwrap_err();
-    assert_eq!(err, ContractError::NotOnOgList {});
-
-    // random can't add to og list
+        .unwrap();
+    let buyer = mock_info("buyer", &coins(PUBLIC_PRICE as u128, "usei"));
     let err = contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            random.clone(),
-            ExecuteMsg::AddToOgList {
-                addresses: vec!["random".to_string()],
+            buyer,
+            ExecuteMsg::MintPublic {
+                quantity: 1,
+                extension: None,
             },
         )
         .unwrap_err();
-    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    assert_eq!(err, ContractError::MaxSupplyReached {});
 
-    // Admin can add to og list
-    let _ = contract
+    // Team mints draw the reservation down
+    contract
         .execute(
             deps.as_mut(),
             mock_env(),
             admin.clone(),
-            ExecuteMsg::AddToOgList {
-                addresses: vec!["random".to_string(), "user".to_string()],
+            ExecuteMsg::MintTeam {
+                quantity: 2,
+                extension: None,
+                transferable: None,
             },
         )
         .unwrap();
+    let res: ReservedSupplyResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::ReservedSupply {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.reserved_supply, COLLECTION_SIZE - 5);
 
-    // Random can't mint more than max
+    // Collection size must cover the reservation
     let err = contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            random.clone(),
-            ExecuteMsg::MintOg {
-                quantity: 2,
-                extension: None,
+            admin.clone(),
+            ExecuteMsg::SetCollectionSize {
+                collection_size: COLLECTION_SIZE - 1,
             },
         )
         .unwrap_err();
-    assert_eq!(err, ContractError::MaxMintReached {});
+    assert_eq!(err, ContractError::InvalidCollectionSize {});
 
-    // Cannot mint without funds
-    let err = contract
+    contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            random,
-            ExecuteMsg::MintOg {
-                quantity: 1,
-                extension: None,
+            admin,
+            ExecuteMsg::MintTeam {
+                quantity: COLLECTION_SIZE - 5,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    let res: ReservedSupplyResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::ReservedSupply {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.reserved_supply, 0);
+}
+
+#[test]
+fn compact_mint_matches_regular_storage() {
+    let mut regular = mock_dependencies();
+    let mut compact = mock_dependencies();
+    let contract = setup_contract(regular.as_mut());
+    setup_contract(compact.as_mut());
+    let admin = mock_info(ADMIN, &[]);
+
+    // Random can't switch the mode
+    let err = contract
+        .execute(
+            compact.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            ExecuteMsg::SetCompactMint { enabled: true },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            compact.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetCompactMint { enabled: true },
+        )
+        .unwrap();
+
+    let buyer = mock_info("buyer", &coins(3 * PUBLIC_PRICE as u128, "usei"));
+    let msgs = [
+        (admin.clone(), ExecuteMsg::SetPublicSale { open: true }),
+        (
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 12,
+                extension: None,
+                transferable: None,
+            },
+        ),
+        (
+            buyer,
+            ExecuteMsg::MintPublic {
+                quantity: 3,
+                extension: None,
+            },
+        ),
+        (
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 5,
+                extension: None,
+                transferable: None,
+            },
+        ),
+        (
+            admin.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "5".to_string(),
+            },
+        ),
+        (
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "spender".to_string(),
+                token_id: "8".to_string(),
+                expires: None,
+            },
+        ),
+        (
+            mock_info("buyer", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: ADMIN.to_string(),
+                token_id: "12".to_string(),
+            },
+        ),
+    ];
+    for (info, msg) in msgs {
+        let res = contract
+            .execute(regular.as_mut(), mock_env(), info.clone(), msg.clone())
+            .unwrap();
+        let compact_res = contract
+            .execute(compact.as_mut(), mock_env(), info, msg)
+            .unwrap();
+        assert_eq!(res, compact_res);
+    }
+
+    // Only transferred or approved tokens left their batch
+    let unpacked: Vec<String> = contract
+        .tokens
+        .keys(compact.as_ref().storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()
+        .unwrap();
+    assert_eq!(unpacked, vec!["12", "5", "8"]);
+
+    let query = |msg: QueryMsg<Empty>| {
+        let res = contract.query(regular.as_ref(), mock_env(), msg.clone());
+        let compact_res = contract.query(compact.as_ref(), mock_env(), msg.clone());
+        assert_eq!(res, compact_res, "{msg:?}");
+    };
+    for token_id in (0..22).map(|id| id.to_string()).chain(["05".to_string()]) {
+        query(QueryMsg::OwnerOf {
+            token_id: token_id.clone(),
+            include_expired: None,
+        });
+        query(QueryMsg::NftInfo {
+            token_id: token_id.clone(),
+        });
+        query(QueryMsg::AllNftInfo {
+            token_id: token_id.clone(),
+            include_expired: None,
+        });
+        query(QueryMsg::Approvals {
+            token_id,
+            include_expired: None,
+        });
+    }
+
+    let cursors = [
+        None,
+        Some("0"),
+        Some("4"),
+        Some("5"),
+        Some("13"),
+        Some("19"),
+        Some("x"),
+    ];
+    for order in [None, Some(OrderBy::Desc)] {
+        for limit in [None, Some(3), Some(100)] {
+            for cursor in cursors {
+                for end_before in cursors {
+                    let cursor = cursor.map(String::from);
+                    let end_before = end_before.map(String::from);
+                    query(QueryMsg::AllTokens {
+                        start_after: cursor.clone(),
+                        start_at: None,
+                        end_before: end_before.clone(),
+                        order,
+                        limit,
+                    });
+                    for owner in [ADMIN, "buyer"] {
+                        query(QueryMsg::Tokens {
+                            owner: owner.to_string(),
+                            start_after: None,
+                            start_at: cursor.clone(),
+                            end_before: end_before.clone(),
+                            order,
+                            limit,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mint_team() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mint_msg = ExecuteMsg::MintTeam {
+        quantity: 10,
+        extension: None,
+        transferable: None,
+    };
+
+    // random cannot mint
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), random, mint_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // can't mint 0
+    let allowed = mock_info(ADMIN, &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            allowed.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 0,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidQuantity {});
+
+    // admin can mint
+    let allowed = mock_info(ADMIN, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), allowed.clone(), mint_msg)
+        .unwrap();
+
+    // ensure num tokens increases
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!(10, count.count);
+
+    // ensure nft info is correct
+    let info = contract.nft_info(deps.as_ref(), "0".to_string()).unwrap();
+    let expected_token_uri = format!("{}/{}", BASE_TOKEN_URI, "0");
+    assert_eq!(
+        info,
+        NftInfoResponse::<Extension> {
+            token_uri: Some(expected_token_uri),
+            extension: None,
+        }
+    );
+
+    let all_info: AllNftInfoResponse<Option<Empty>> = contract
+        .all_nft_info(deps.as_ref(), mock_env(), "8".to_string(), true)
+        .unwrap();
+    let expected_token_uri = format!("{}/{}", BASE_TOKEN_URI, "8");
+    assert_eq!(
+        all_info,
+        AllNftInfoResponse::<Extension> {
+            access: OwnerOfResponse {
+                owner: ADMIN.to_string(),
+                approvals: vec![],
+            },
+            info: NftInfoResponse {
+                token_uri: Some(expected_token_uri),
+                extension: None,
+            }
+        }
+    );
+
+    // ensure owner info is correct
+    let owner = contract
+        .owner_of(deps.as_ref(), mock_env(), "0".to_string(), true)
+        .unwrap();
+    assert_eq!(
+        owner,
+        OwnerOfResponse {
+            owner: ADMIN.to_string(),
+            approvals: vec![],
+        }
+    );
+
+    // can't mint past collection size
+    let mint_msg = ExecuteMsg::MintTeam {
+        quantity: 91,
+        extension: None,
+        transferable: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), allowed, mint_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MaxSupplyReached {});
+}
+
+#[test]
+fn mint_og() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Open og minting
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetOgSale { open: true },
+        )
+        .unwrap();
+
+    // random can't mint if not og
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::MintOg {
+                quantity: 1,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOnOgList {});
+
+    // random can't add to og list
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::AddToOgList {
+                entries: vec![("random".to_string(), 1)],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // Admin can add to og list
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::AddToOgList {
+                entries: vec![("random".to_string(), 1), ("user".to_string(), 1)],
+            },
+        )
+        .unwrap();
+
+    // Random can't mint more than max
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::MintOg {
+                quantity: 2,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::MaxMintReached {});
+
+    // Cannot mint without funds
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random,
+            ExecuteMsg::MintOg {
+                quantity: 1,
+                extension: None,
             },
         )
         .unwrap_err();
@@ -687,7 +1194,7 @@ fn mint_allowlist() {
             mock_env(),
             random.clone(),
             ExecuteMsg::AddToAllowlist {
-                addresses: vec!["random".to_string()],
+                entries: vec![("random".to_string(), 1)],
             },
         )
         .unwrap_err();
@@ -700,10 +1207,10 @@ fn mint_allowlist() {
             mock_env(),
             admin.clone(),
             ExecuteMsg::AddToAllowlist {
-                addresses: vec![
-                    "random".to_string(),
-                    "user".to_string(),
-                    "user_2".to_string(),
+                entries: vec![
+                    ("random".to_string(), 1),
+                    ("user".to_string(), 1),
+                    ("user_2".to_string(), 1),
                 ],
             },
         )
@@ -819,7 +1326,7 @@ fn mint_allowlist() {
             mock_env(),
             admin.clone(),
             ExecuteMsg::AddToOgList {
-                addresses: vec!["og_user".to_string(), "user_2".to_string()],
+                entries: vec![("og_user".to_string(), 1), ("user_2".to_string(), 1)],
             },
         )
         .unwrap();
@@ -895,35 +1402,176 @@ fn mint_allowlist() {
 }
 
 #[test]
-fn mint_public() {
+fn allowlist_allocations() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
-
-    // Open public minting
     let admin = mock_info(ADMIN, &[]);
-    contract
+
+    // allocations must be positive
+    let err = contract
         .execute(
             deps.as_mut(),
             mock_env(),
             admin.clone(),
-            ExecuteMsg::SetPublicSale { open: true },
+            ExecuteMsg::AddToAllowlist {
+                entries: vec![("top".to_string(), 3), ("user".to_string(), 0)],
+            },
         )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidQuantity {});
+    let res = contract
+        .allocation(deps.as_ref(), "top".to_string())
         .unwrap();
+    assert_eq!(
+        res,
+        AllocationResponse {
+            og: 0,
+            allowlist: 0
+        }
+    );
 
-    // random can't mint over the max limit
-    let random = mock_info("random", &[]);
-    let err = contract
+    contract
         .execute(
             deps.as_mut(),
             mock_env(),
-            random,
-            ExecuteMsg::MintPublic {
-                quantity: 6,
-                extension: None,
+            admin.clone(),
+            ExecuteMsg::AddToAllowlist {
+                entries: vec![("top".to_string(), 3), ("user".to_string(), 1)],
             },
         )
-        .unwrap_err();
-    assert_eq!(err, ContractError::MaxMintReached {});
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SetAllowlistSale { open: true },
+        )
+        .unwrap();
+
+    // each mint uses up part of the allocation, within the per mint maximum
+    let top = mock_info("top", &coins(ALLOWLIST_PRICE.into(), "usei"));
+    for remaining in [2, 1] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                top.clone(),
+                ExecuteMsg::MintAllowlist {
+                    quantity: 1,
+                    extension: None,
+                },
+            )
+            .unwrap();
+        let res = contract
+            .allocation(deps.as_ref(), "top".to_string())
+            .unwrap();
+        assert_eq!(res.allowlist, remaining);
+    }
+
+    let res: AllocationsResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Allowlist {
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.allocations,
+        vec![
+            Allocation {
+                address: "top".to_string(),
+                remaining: 1,
+            },
+            Allocation {
+                address: "user".to_string(),
+                remaining: 1,
+            },
+        ]
+    );
+
+    // a used up allocation removes the address from the list
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            top.clone(),
+            ExecuteMsg::MintAllowlist {
+                quantity: 1,
+                extension: None,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            top,
+            ExecuteMsg::MintAllowlist {
+                quantity: 1,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOnAllowlist {});
+    let res = contract
+        .allocations(
+            deps.as_ref(),
+            &contract.allowlist,
+            PageRange::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.allocations.len(), 1);
+    assert_eq!(
+        contract
+            .tokens(deps.as_ref(), "top".to_string(), None, None)
+            .unwrap()
+            .tokens
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn mint_public() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Open public minting
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetPublicSale { open: true },
+        )
+        .unwrap();
+
+    // random can't mint over the max limit
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random,
+            ExecuteMsg::MintPublic {
+                quantity: 6,
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::MaxMintReached {});
 
     // can't mint without funds
     let funds = coins(100000, "ibc/test");
@@ -963,6 +1611,7 @@ fn mint_public() {
     let mint_msg = ExecuteMsg::MintTeam {
         quantity: 95,
         extension: None,
+        transferable: None,
     };
     let _ = contract
         .execute(deps.as_mut(), mock_env(), admin, mint_msg)
@@ -1084,6 +1733,7 @@ fn update_admin() {
     let mint_msg = ExecuteMsg::MintTeam {
         quantity: 10,
         extension: None,
+        transferable: None,
     };
     // Old owner cannot team mint
     let err: ContractError = contract
@@ -1112,6 +1762,7 @@ fn burning() {
             ExecuteMsg::MintTeam {
                 quantity: 1,
                 extension: None,
+                transferable: None,
             },
         )
         .unwrap();
@@ -1728,6 +2379,21 @@ fn update_withdraw_address() {
         .withdraw_address
         .load(deps.as_ref().storage)
         .unwrap();
+    assert_eq!(withdraw_address, ADMIN.to_string());
+
+    // anyone can apply it once the timelock has passed
+    contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            random,
+            ExecuteMsg::ApplyChange { id: 1 },
+        )
+        .unwrap();
+    let withdraw_address = contract
+        .withdraw_address
+        .load(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(withdraw_address, "new".to_string())
 }
 
@@ -1758,6 +2424,14 @@ fn test_remove_withdraw_address() {
             ExecuteMsg::RemoveWithdrawAddress {},
         )
         .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            admin.clone(),
+            ExecuteMsg::ApplyChange { id: 1 },
+        )
+        .unwrap();
 
     // validate withdraw is removed
     let withdraw_address: Option<String> = from_json(
@@ -1779,6 +2453,14 @@ fn test_remove_withdraw_address() {
             },
         )
         .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            admin.clone(),
+            ExecuteMsg::ApplyChange { id: 2 },
+        )
+        .unwrap();
     let withdraw_address: Option<String> = from_json(
         contract
             .query(deps.as_ref(), mock_env(), QueryMsg::WithdrawAddress {})
@@ -1795,7 +2477,7 @@ fn withdraw_funds() {
 
     let amount = coin(100000, "usei");
     let res = contract
-        .withdraw_funds(deps.as_mut().storage, &amount)
+        .withdraw_funds(deps.as_ref(), &mock_env(), &amount)
         .unwrap();
     assert_eq!(
         res,
@@ -1819,8 +2501,16 @@ fn withdraw_funds() {
             ExecuteMsg::RemoveWithdrawAddress {},
         )
         .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            admin.clone(),
+            ExecuteMsg::ApplyChange { id: 1 },
+        )
+        .unwrap();
     let err = contract
-        .withdraw_funds(deps.as_mut().storage, &Coin::new(100, "usei"))
+        .withdraw_funds(deps.as_ref(), &mock_env(), &Coin::new(100, "usei"))
         .unwrap_err();
     assert_eq!(err, ContractError::NoWithdrawAddress {});
 }
@@ -1915,3 +2605,2190 @@ fn query_tokens_by_owner() {
         .unwrap();
     assert_eq!(&by_minter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn soulbound_at_mint() {
+    for compact in [false, true] {
+        let mut deps = mock_dependencies();
+        let contract = setup_contract(deps.as_mut());
+        let admin = mock_info(ADMIN, &[]);
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                admin.clone(),
+                ExecuteMsg::SetCompactMint { enabled: compact },
+            )
+            .unwrap();
+
+        // Badges are soulbound from the mint, the rest follows the collection flag
+        let mint = |transferable| ExecuteMsg::MintTeam {
+            quantity: 3,
+            extension: None,
+            transferable,
+        };
+        contract
+            .execute(deps.as_mut(), mock_env(), admin.clone(), mint(Some(false)))
+            .unwrap();
+        contract
+            .execute(deps.as_mut(), mock_env(), admin.clone(), mint(None))
+            .unwrap();
+        let transferable = |deps: Deps, token_id: &str| {
+            let info: NftInfoResponse<NftInfoExtension<Extension>> = from_json(
+                contract
+                    .query(
+                        deps,
+                        mock_env(),
+                        QueryMsg::NftInfo {
+                            token_id: token_id.to_string(),
+                        },
+                    )
+                    .unwrap(),
+            )
+            .unwrap();
+            info.extension.transferable
+        };
+        assert!(!transferable(deps.as_ref(), "0"));
+        assert!(!transferable(deps.as_ref(), "2"));
+        assert!(transferable(deps.as_ref(), "3"));
+
+        let transfer = |token_id: &str| ExecuteMsg::TransferNft {
+            recipient: "random".to_string(),
+            token_id: token_id.to_string(),
+        };
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), admin.clone(), transfer("0"))
+            .unwrap_err();
+        assert_eq!(err, ContractError::NotTransferable {});
+        contract
+            .execute(deps.as_mut(), mock_env(), admin.clone(), transfer("3"))
+            .unwrap();
+
+        // Flags set later still win, and the mint flag survives approval changes
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                admin.clone(),
+                ExecuteMsg::SetTransferable {
+                    token_id: Some("1".to_string()),
+                    transferable: true,
+                },
+            )
+            .unwrap();
+        contract
+            .execute(deps.as_mut(), mock_env(), admin.clone(), transfer("1"))
+            .unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                admin.clone(),
+                ExecuteMsg::Revoke {
+                    spender: "spender".to_string(),
+                    token_id: "2".to_string(),
+                },
+            )
+            .unwrap();
+        assert!(!transferable(deps.as_ref(), "2"));
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), admin, transfer("2"))
+            .unwrap_err();
+        assert_eq!(err, ContractError::NotTransferable {});
+    }
+}
+
+#[test]
+fn soulbound_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a couple tokens
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 2,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+
+    // random cannot change transferability
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::SetTransferable {
+                token_id: None,
+                transferable: false,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // can't set transferability of a token that doesn't exist
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetTransferable {
+                token_id: Some("2".to_string()),
+                transferable: false,
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::NotFound { .. })));
+
+    // Make token 0 soulbound
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetTransferable {
+                token_id: Some("0".to_string()),
+                transferable: false,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "set_transferable")
+            .add_attribute("token_id", "0")
+            .add_attribute("transferable", "false")
+    );
+
+    // Flag is exposed in nft info
+    let info: NftInfoResponse<NftInfoExtension<Extension>> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NftInfo {
+                    token_id: "0".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        info,
+        NftInfoResponse {
+            token_uri: Some(format!("{}/{}", BASE_TOKEN_URI, "0")),
+            extension: NftInfoExtension {
+                transferable: false,
+                extension: None,
+            },
+        }
+    );
+    let info: AllNftInfoResponse<NftInfoExtension<Extension>> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllNftInfo {
+                    token_id: "1".to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(info.info.extension.transferable);
+
+    // Soulbound token can't be transferred, sent or approved
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: "random".to_string(),
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SendNft {
+                contract: "another_contract".to_string(),
+                token_id: "0".to_string(),
+                msg: to_json_binary("msg").unwrap(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "random".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+
+    // Other tokens still transfer
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: "random".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Make the whole collection soulbound
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetTransferable {
+                token_id: None,
+                transferable: false,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: ADMIN.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::ApproveAll {
+                operator: "operator".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+
+    // Token level flag overrides the collection
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SetTransferable {
+                token_id: Some("1".to_string()),
+                transferable: true,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random,
+            ExecuteMsg::TransferNft {
+                recipient: ADMIN.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn operator_filter() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 2,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+
+    // Filter is disabled by default
+    let res: OperatorFilterResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::OperatorFilter {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.mode, OperatorFilterMode::Disabled);
+
+    // Approve a marketplace before the filter is turned on
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "marketplace".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+    // random cannot update the registry or the filter
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::AddToOperatorRegistry {
+                addresses: vec!["royalty_market".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random,
+            ExecuteMsg::SetOperatorFilter {
+                mode: OperatorFilterMode::Allowlist,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // Only allow the royalty enforcing marketplace
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::AddToOperatorRegistry {
+                addresses: vec!["royalty_market".to_string()],
+            },
+        )
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetOperatorFilter {
+                mode: OperatorFilterMode::Allowlist,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "set_operator_filter")
+            .add_attribute("mode", "allowlist")
+    );
+    let res: OperatorRegistryResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OperatorRegistry {
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.operators, vec!["royalty_market".to_string()]);
+
+    // Unlisted spenders and operators can't be approved
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "marketplace".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorNotAllowed {
+            operator: "marketplace".to_string()
+        }
+    );
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::ApproveAll {
+                operator: "marketplace".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorNotAllowed {
+            operator: "marketplace".to_string()
+        }
+    );
+
+    // Existing approval can no longer be used
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("marketplace", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorNotAllowed {
+            operator: "marketplace".to_string()
+        }
+    );
+
+    // Listed operator can be approved and transfer
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::ApproveAll {
+                operator: "royalty_market".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("royalty_market", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Blocklist mode rejects listed operators only
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetOperatorFilter {
+                mode: OperatorFilterMode::Blocklist,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("marketplace", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[]),
+            ExecuteMsg::Approve {
+                spender: "royalty_market".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OperatorNotAllowed {
+            operator: "royalty_market".to_string()
+        }
+    );
+
+    // Removing from the registry and disabling the filter lifts restrictions
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::RemoveFromOperatorRegistry {
+                addresses: vec!["royalty_market".to_string()],
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[]),
+            ExecuteMsg::Approve {
+                spender: "royalty_market".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SetOperatorFilter {
+                mode: OperatorFilterMode::Disabled,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn batch_transferring_and_sending_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 3,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "random".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+    // can't send an empty batch
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec![],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidQuantity {});
+
+    // random is only approved for one of the tokens, so nothing is transferred
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random,
+            ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // owner can
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_transfer_nft")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("recipient", "random")
+            .add_attribute("token_ids", "0,1")
+    );
+    let tokens = contract
+        .tokens(deps.as_ref(), "random".to_string(), None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["0".to_string(), "1".to_string()]);
+
+    // send one receive message per token
+    let msg = to_json_binary("msg").unwrap();
+    let owner = mock_info("random", &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            ExecuteMsg::BatchSendNft {
+                contract: "game".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+                msg: msg.clone(),
+                aggregate: None,
+            },
+        )
+        .unwrap();
+    let expected: Vec<CosmosMsg> = ["0", "1"]
+        .iter()
+        .map(|token_id| {
+            Cw721ReceiveMsg {
+                sender: "random".to_string(),
+                token_id: token_id.to_string(),
+                msg: msg.clone(),
+            }
+            .into_cosmos_msg("game")
+            .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_messages(expected)
+            .add_attribute("action", "batch_send_nft")
+            .add_attribute("sender", "random")
+            .add_attribute("recipient", "game")
+            .add_attribute("token_ids", "0,1")
+    );
+
+    // send one aggregated receive message
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::BatchSendNft {
+                contract: "game".to_string(),
+                token_ids: vec!["2".to_string()],
+                msg: msg.clone(),
+                aggregate: Some(true),
+            },
+        )
+        .unwrap();
+    let expected: CosmosMsg = Cw721BatchReceiveMsg {
+        sender: ADMIN.to_string(),
+        token_ids: vec!["2".to_string()],
+        msg,
+    }
+    .into_cosmos_msg("game")
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, expected);
+    let tokens = contract
+        .tokens(deps.as_ref(), "game".to_string(), None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens.len(), 3);
+}
+
+#[test]
+fn batch_approving_revoking() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 3,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: "random".to_string(),
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+
+    // can't approve a token owned by someone else, nothing is approved
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchApprove {
+                spender: "marketplace".to_string(),
+                token_ids: vec!["0".to_string(), "2".to_string()],
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    let res = contract
+        .approvals(deps.as_ref(), mock_env(), "0".to_string(), true)
+        .unwrap();
+    assert!(res.approvals.is_empty());
+
+    // approve token 0 first, so the batch only changes token 1
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Approve {
+                spender: "marketplace".to_string(),
+                token_id: "0".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchApprove {
+                spender: "marketplace".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+                expires: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_approve")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("spender", "marketplace")
+            .add_attribute("token_ids", "1")
+    );
+    for token_id in ["0", "1"] {
+        let res = contract
+            .approval(
+                deps.as_ref(),
+                mock_env(),
+                token_id.to_string(),
+                "marketplace".to_string(),
+                false,
+            )
+            .unwrap();
+        assert_eq!(res.approval.expires, Expiration::Never {});
+    }
+
+    // revoke all of them
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::BatchRevoke {
+                spender: "marketplace".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_revoke")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("spender", "marketplace")
+            .add_attribute("token_ids", "0,1")
+    );
+    let res = contract
+        .approvals(deps.as_ref(), mock_env(), "1".to_string(), true)
+        .unwrap();
+    assert!(res.approvals.is_empty());
+}
+
+#[test]
+fn query_tokens_with_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 3,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::TransferNft {
+                recipient: "random".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SetTransferable {
+                token_id: Some("2".to_string()),
+                transferable: false,
+            },
+        )
+        .unwrap();
+
+    let token_with_info = |token_id: &str, owner: &str, transferable: bool| TokenWithInfo {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: Some(format!("{}/{}", BASE_TOKEN_URI, token_id)),
+        extension: NftInfoExtension {
+            transferable,
+            extension: None,
+        },
+    };
+
+    // all tokens, paginated
+    let res: TokensWithInfoResponse<NftInfoExtension<Extension>> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllTokensWithInfo {
+                    start_after: None,
+                    limit: Some(2),
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.tokens,
+        vec![
+            token_with_info("0", ADMIN, true),
+            token_with_info("1", "random", true),
+        ]
+    );
+    let res = contract
+        .all_tokens_with_info(
+            deps.as_ref(),
+            PageRange::start_after(Some("1".to_string())),
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec![token_with_info("2", ADMIN, false)]);
+
+    // tokens by owner
+    let res: TokensWithInfoResponse<NftInfoExtension<Extension>> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TokensWithInfo {
+                    owner: ADMIN.to_string(),
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.tokens,
+        vec![
+            token_with_info("0", ADMIN, true),
+            token_with_info("2", ADMIN, false),
+        ]
+    );
+}
+
+#[test]
+fn query_tokens_numeric_order() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 12,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["2".to_string(), "10".to_string(), "11".to_string()],
+            },
+        )
+        .unwrap();
+
+    // enumeration follows mint order rather than string order
+    let tokens = contract
+        .all_tokens(deps.as_ref(), Some("8".to_string()), None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["9", "10", "11"]);
+    let tokens = contract
+        .all_tokens(deps.as_ref(), Some("1".to_string()), Some(2))
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["2", "3"]);
+
+    let tokens = contract
+        .tokens(deps.as_ref(), "random".to_string(), None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["2", "10", "11"]);
+    let tokens = contract
+        .tokens(
+            deps.as_ref(),
+            "random".to_string(),
+            Some("2".to_string()),
+            None,
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["10", "11"]);
+}
+
+#[test]
+fn query_order_and_range() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 12,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+
+    // descending pages walk backwards from the cursor
+    let tokens: TokensResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllTokens {
+                    start_after: None,
+                    limit: Some(3),
+                    start_at: None,
+                    end_before: None,
+                    order: Some(OrderBy::Desc),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tokens.tokens, vec!["11", "10", "9"]);
+    let range = PageRange {
+        start_after: Some("9".to_string()),
+        order: Some(OrderBy::Desc),
+        ..PageRange::default()
+    };
+    let tokens = contract
+        .all_tokens_in_range(deps.as_ref(), range, Some(3))
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["8", "7", "6"]);
+
+    // start_at is inclusive and wins over start_after, end_before is exclusive
+    let range = PageRange {
+        start_after: Some("1".to_string()),
+        start_at: Some("2".to_string()),
+        end_before: Some("5".to_string()),
+        order: None,
+    };
+    let tokens = contract
+        .tokens_in_range(deps.as_ref(), ADMIN.to_string(), range, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["2", "3", "4"]);
+    let range = PageRange {
+        start_at: Some("10".to_string()),
+        end_before: Some("8".to_string()),
+        order: Some(OrderBy::Desc),
+        ..PageRange::default()
+    };
+    let tokens = contract
+        .tokens_in_range(deps.as_ref(), ADMIN.to_string(), range, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["10", "9"]);
+
+    // operators iterate by address
+    for operator in ["operator_a", "operator_b", "operator_c"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                admin.clone(),
+                ExecuteMsg::ApproveAll {
+                    operator: operator.to_string(),
+                    expires: None,
+                },
+            )
+            .unwrap();
+    }
+    let res: OperatorsResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllOperators {
+                    owner: ADMIN.to_string(),
+                    include_expired: None,
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: Some("operator_a".to_string()),
+                    order: Some(OrderBy::Desc),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    let operators: Vec<_> = res.operators.into_iter().map(|op| op.spender).collect();
+    assert_eq!(operators, vec!["operator_c", "operator_b"]);
+}
+
+#[test]
+fn migrate_numeric_token_indexes() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // tokens saved before the numeric indexes existed
+    let legacy_tokens: Map<&str, TokenInfo<Extension>> = Map::new("tokens");
+    for token_id in ["0", "1", "10"] {
+        let token = TokenInfo {
+            owner: Addr::unchecked("minter"),
+            approvals: vec![],
+            extension: None,
+        };
+        legacy_tokens
+            .save(deps.as_mut().storage, token_id, &token)
+            .unwrap();
+    }
+    let tokens = contract.all_tokens(deps.as_ref(), None, None).unwrap();
+    assert!(tokens.tokens.is_empty());
+
+    cw2::set_contract_version(deps.as_mut().storage, crate::CONTRACT_NAME, "0.1.0").unwrap();
+    let res = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", "0.1.0")
+            .add_attribute("to_version", crate::CONTRACT_VERSION)
+            .add_attribute("step", "0.2.0")
+            .add_attribute("reindexed_tokens", "3")
+            .add_attribute("num_holders", "1")
+            .add_attribute("step", "0.3.0")
+            .add_attribute("og_allocations", "0")
+            .add_attribute("allowlist_allocations", "0")
+    );
+
+    let tokens = contract.all_tokens(deps.as_ref(), None, None).unwrap();
+    assert_eq!(tokens.tokens, vec!["0", "1", "10"]);
+    let tokens = contract
+        .tokens(deps.as_ref(), "minter".to_string(), None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["0", "1", "10"]);
+    let balance = contract
+        .balance_of(deps.as_ref(), "minter".to_string())
+        .unwrap();
+    assert_eq!(balance.balance, 3);
+}
+
+#[test]
+fn migrate_versions() {
+    let mut deps = mock_dependencies();
+    setup_contract(deps.as_mut());
+
+    // steps at or below the stored version are skipped
+    cw2::set_contract_version(deps.as_mut().storage, crate::CONTRACT_NAME, "0.3.0").unwrap();
+    let res = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+    assert!(!res.attributes.iter().any(|attr| attr.key == "step"));
+    let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.version, crate::CONTRACT_VERSION);
+
+    // downgrades are rejected
+    cw2::set_contract_version(deps.as_mut().storage, crate::CONTRACT_NAME, "99.0.0").unwrap();
+    let err = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CannotDowngrade {
+            from: "99.0.0".to_string(),
+            to: crate::CONTRACT_VERSION.to_string(),
+        }
+    );
+
+    // so are other contracts and unparseable versions
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+    let err = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Version(cw2::VersionError::WrongContract {
+            expected: crate::CONTRACT_NAME.to_string(),
+            found: "crates.io:cw20-base".to_string(),
+        })
+    );
+    cw2::set_contract_version(deps.as_mut().storage, crate::CONTRACT_NAME, "v1").unwrap();
+    let err = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert!(matches!(err, ContractError::SemVer(_)));
+}
+
+#[test]
+fn upgrade_steps_are_ordered_and_released() {
+    let to: semver::Version = crate::CONTRACT_VERSION.parse().unwrap();
+    let versions: Vec<semver::Version> = crate::upgrades::steps::<Extension, Empty, Empty, Empty>()
+        .into_iter()
+        .map(|(version, _)| version.parse().unwrap())
+        .collect();
+    assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(versions.iter().all(|version| *version <= to));
+}
+
+#[test]
+fn migrate_allowlist_allocations() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // lists saved before allocations existed
+    let legacy_og: Map<&Addr, bool> = Map::new("og_list_key");
+    let legacy_allowlist: Map<&Addr, bool> = Map::new("allowlist");
+    for addr in ["og_user", "both"] {
+        legacy_og
+            .save(deps.as_mut().storage, &Addr::unchecked(addr), &true)
+            .unwrap();
+    }
+    legacy_allowlist
+        .save(deps.as_mut().storage, &Addr::unchecked("both"), &true)
+        .unwrap();
+
+    cw2::set_contract_version(deps.as_mut().storage, crate::CONTRACT_NAME, "0.2.0").unwrap();
+    let res = entry::migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", "0.2.0")
+            .add_attribute("to_version", crate::CONTRACT_VERSION)
+            .add_attribute("step", "0.3.0")
+            .add_attribute("og_allocations", "2")
+            .add_attribute("allowlist_allocations", "1")
+    );
+
+    let res = contract
+        .allocation(deps.as_ref(), "both".to_string())
+        .unwrap();
+    assert_eq!(
+        res,
+        AllocationResponse {
+            og: MAX_PER_OG,
+            allowlist: MAX_PER_ALLOWLIST,
+        }
+    );
+    let res = contract
+        .allocation(deps.as_ref(), "og_user".to_string())
+        .unwrap();
+    assert_eq!(res.og, MAX_PER_OG);
+}
+
+#[test]
+fn query_balances_and_holders() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 3,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    let res: BalanceResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Balance {
+                    owner: ADMIN.to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.balance, 3);
+
+    // transfers move balances and add new holders
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchTransferNft {
+                recipient: "venus".to_string(),
+                token_ids: vec!["0".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("venus", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "mars".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let res: HoldersResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Holders {
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.holders,
+        vec![
+            Holder {
+                address: ADMIN.to_string(),
+                balance: 1,
+            },
+            Holder {
+                address: "mars".to_string(),
+                balance: 1,
+            },
+            Holder {
+                address: "venus".to_string(),
+                balance: 1,
+            },
+        ]
+    );
+    let range = PageRange::start_after(Some("mars".to_string()));
+    let res = contract.holders(deps.as_ref(), range, None).unwrap();
+    assert_eq!(res.holders.len(), 1);
+    let range = PageRange {
+        end_before: Some("mars".to_string()),
+        order: Some(OrderBy::Desc),
+        ..PageRange::default()
+    };
+    let res = contract.holders(deps.as_ref(), range, None).unwrap();
+    assert_eq!(res.holders.len(), 1);
+    assert_eq!(res.holders[0].address, "venus");
+
+    // holders who transfer their last token are dropped
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::TransferNft {
+                recipient: "mars".to_string(),
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+    let res: NumHoldersResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::NumHolders {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.count, 2);
+    let res = contract
+        .balance_of(deps.as_ref(), ADMIN.to_string())
+        .unwrap();
+    assert_eq!(res.balance, 0);
+    let res = contract
+        .holders(deps.as_ref(), PageRange::default(), None)
+        .unwrap();
+    assert_eq!(
+        res.holders,
+        vec![
+            Holder {
+                address: "mars".to_string(),
+                balance: 2,
+            },
+            Holder {
+                address: "venus".to_string(),
+                balance: 1,
+            },
+        ]
+    );
+}
+
+fn upstream_migrate_msg(collection_size: u64) -> MigrateMsg {
+    MigrateMsg {
+        from_upstream: Some(UpstreamMigrateMsg {
+            base_token_uri: BASE_TOKEN_URI.to_string(),
+            collection_size,
+            og_price: Uint128::from(OG_PRICE),
+            allowlist_price: Uint128::from(ALLOWLIST_PRICE),
+            public_price: Uint128::from(PUBLIC_PRICE),
+            max_per_og: MAX_PER_OG,
+            max_per_allowlist: MAX_PER_ALLOWLIST,
+            max_per_public: MAX_PER_PUBLIC,
+            withdraw_address: None,
+        }),
+    }
+}
+
+#[test]
+fn migrate_from_upstream_v0_18() {
+    let mut deps = mock_dependencies();
+    let admin = mock_info(ADMIN, &[]);
+    v18::entry::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        admin.clone(),
+        v18::InstantiateMsg {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            minter: ADMIN.to_string(),
+        },
+    )
+    .unwrap();
+    for (token_id, owner) in [("0", "venus"), ("1", "venus"), ("7", "mars")] {
+        v18::entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            v18::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: (token_id != "7").then(|| format!("{BASE_TOKEN_URI}/{token_id}")),
+                extension: None,
+            },
+        )
+        .unwrap();
+    }
+    let venus = mock_info("venus", &[]);
+    v18::entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        venus.clone(),
+        v18::ExecuteMsg::Approve {
+            spender: "random".to_string(),
+            token_id: "1".to_string(),
+            expires: None,
+        },
+    )
+    .unwrap();
+    v18::entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        venus,
+        v18::ExecuteMsg::ApproveAll {
+            operator: "operator".to_string(),
+            expires: None,
+        },
+    )
+    .unwrap();
+
+    // the collection size has to fit the existing token ids
+    let err = entry::migrate(deps.as_mut(), mock_env(), upstream_migrate_msg(7)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCollectionSize {});
+
+    let res = entry::migrate(deps.as_mut(), mock_env(), upstream_migrate_msg(10)).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", "0.18.0")
+            .add_attribute("to_version", crate::CONTRACT_VERSION)
+            .add_attribute("migrated_tokens", "3")
+            .add_attribute("token_count", "8")
+    );
+    let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.version, crate::CONTRACT_VERSION);
+
+    // tokens, approvals and operators are kept
+    let contract = Cw721Contract::<Extension, Empty, Empty, Empty>::default();
+    let tokens = contract.all_tokens(deps.as_ref(), None, None).unwrap();
+    assert_eq!(tokens.tokens, vec!["0", "1", "7"]);
+    let owner = contract
+        .owner_of(deps.as_ref(), mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(
+        owner,
+        OwnerOfResponse {
+            owner: "venus".to_string(),
+            approvals: vec![Approval {
+                spender: "random".to_string(),
+                expires: Expiration::Never {},
+            }],
+        }
+    );
+    let operator = contract
+        .operator(
+            deps.as_ref(),
+            mock_env(),
+            "venus".to_string(),
+            "operator".to_string(),
+            false,
+        )
+        .unwrap();
+    assert_eq!(operator.approval.spender, "operator");
+    let balance = contract
+        .balance_of(deps.as_ref(), "venus".to_string())
+        .unwrap();
+    assert_eq!(balance.balance, 2);
+
+    // token uris follow the base token uri and new mints continue after the highest id
+    let info = contract.nft_info(deps.as_ref(), "7".to_string()).unwrap();
+    assert_eq!(info.token_uri, Some(format!("{BASE_TOKEN_URI}/7")));
+    let withdraw_address = contract.withdraw_address.load(&deps.storage).unwrap();
+    assert_eq!(withdraw_address, ADMIN);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::MintTeam {
+                quantity: 2,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    let tokens = contract
+        .tokens(deps.as_ref(), ADMIN.to_string(), None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["8", "9"]);
+
+    // collections deployed from this contract can't take the upstream path
+    let err = entry::migrate(deps.as_mut(), mock_env(), upstream_migrate_msg(10)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Version(cw2::VersionError::WrongVersion {
+            expected: "0.18".to_string(),
+            found: crate::CONTRACT_VERSION.to_string(),
+        })
+    );
+}
+
+#[test]
+fn migrate_from_upstream_v0_18_keeps_token_uris_and_withdraw_address() {
+    let mut deps = mock_dependencies();
+    let admin = mock_info(ADMIN, &[]);
+    v18::entry::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        admin.clone(),
+        v18::InstantiateMsg {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            minter: ADMIN.to_string(),
+        },
+    )
+    .unwrap();
+    for (token_id, token_uri) in [
+        ("0", format!("{BASE_TOKEN_URI}/0")),
+        ("1", "ipfs://elsewhere/1".to_string()),
+    ] {
+        v18::entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            v18::ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: "venus".to_string(),
+                token_uri: Some(token_uri),
+                extension: None,
+            },
+        )
+        .unwrap();
+    }
+    let contract = Cw721Contract::<Extension, Empty, Empty, Empty>::default();
+    contract
+        .withdraw_address
+        .save(deps.as_mut().storage, &"treasury".to_string())
+        .unwrap();
+
+    // tokens with their own uri would lose it
+    let err = entry::migrate(deps.as_mut(), mock_env(), upstream_migrate_msg(10)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TokenUriMismatch {
+            token_id: "1".to_string(),
+            expected: format!("{BASE_TOKEN_URI}/1"),
+        }
+    );
+
+    v18::entry::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("venus", &[]),
+        v18::ExecuteMsg::Burn {
+            token_id: "1".to_string(),
+        },
+    )
+    .unwrap();
+    entry::migrate(deps.as_mut(), mock_env(), upstream_migrate_msg(10)).unwrap();
+    let info = contract.nft_info(deps.as_ref(), "0".to_string()).unwrap();
+    assert_eq!(info.token_uri, Some(format!("{BASE_TOKEN_URI}/0")));
+
+    // the stored withdraw address is kept
+    let withdraw_address = contract.withdraw_address.load(&deps.storage).unwrap();
+    assert_eq!(withdraw_address, "treasury");
+}
+
+/// Locks token 0 and records the last token transferred
+struct LockingExtension;
+
+const LAST_TRANSFER: Item<(String, String)> = Item::new("last_transfer");
+
+impl Cw721Extension<Empty, Empty, Empty> for LockingExtension {
+    type Error = ContractError;
+
+    fn execute(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> Result<Response, ContractError> {
+        Ok(Response::new().add_attribute("action", "extension"))
+    }
+
+    fn query(&self, _deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary("extension")
+    }
+
+    fn before_transfer(
+        &self,
+        _deps: DepsMut,
+        _env: &Env,
+        token_id: &str,
+        _recipient: &str,
+    ) -> Result<(), ContractError> {
+        if token_id == "0" {
+            return Err(ContractError::NotTransferable {});
+        }
+        Ok(())
+    }
+
+    fn after_transfer(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        token_id: &str,
+        recipient: &str,
+    ) -> Result<(), ContractError> {
+        LAST_TRANSFER.save(deps.storage, &(token_id.to_string(), recipient.to_string()))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn extension_handler() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 3,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+
+    // extension messages go to the extension
+    let res = contract
+        .execute_with(
+            &LockingExtension,
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Extension { msg: Empty {} },
+        )
+        .unwrap();
+    assert_eq!(res, Response::new().add_attribute("action", "extension"));
+    let res: String = from_json(
+        contract
+            .query_with(
+                &LockingExtension,
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Extension { msg: Empty {} },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, "extension");
+
+    // transfers run the hooks around the base logic
+    let err = contract
+        .execute_with(
+            &LockingExtension,
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::BatchTransferNft {
+                recipient: "venus".to_string(),
+                token_ids: vec!["1".to_string(), "0".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotTransferable {});
+    let owner = contract
+        .owner_of(deps.as_ref(), mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(owner.owner, ADMIN);
+
+    contract
+        .execute_with(
+            &LockingExtension,
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SendNft {
+                contract: "market".to_string(),
+                token_id: "2".to_string(),
+                msg: to_json_binary("sell").unwrap(),
+            },
+        )
+        .unwrap();
+    let last_transfer = LAST_TRANSFER.load(deps.as_ref().storage).unwrap();
+    assert_eq!(last_transfer, ("2".to_string(), "market".to_string()));
+}
+
+#[test]
+fn hook_subscriptions() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // only the owner manages hooks
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            ExecuteMsg::AddHook {
+                contract: "game".to_string(),
+                error_policy: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let admin = mock_info(ADMIN, &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::AddHook {
+                contract: "game".to_string(),
+                error_policy: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("contract", "game")
+            .add_attribute("error_policy", "abort")
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::AddHook {
+                contract: "indexer".to_string(),
+                error_policy: Some(HookErrorPolicy::Ignore),
+            },
+        )
+        .unwrap();
+    let res: HooksResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Hooks {
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.hooks,
+        vec![
+            HookResponse {
+                contract: "game".to_string(),
+                error_policy: HookErrorPolicy::Abort,
+            },
+            HookResponse {
+                contract: "indexer".to_string(),
+                error_policy: HookErrorPolicy::Ignore,
+            },
+        ]
+    );
+
+    // mints notify every hook, failures only revert for aborting hooks
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::MintTeam {
+                quantity: 2,
+                extension: None,
+                transferable: None,
+            },
+        )
+        .unwrap();
+    let mint = Cw721HookMsg::Mint {
+        token_ids: vec!["0".to_string(), "1".to_string()],
+        owner: ADMIN.to_string(),
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "game".to_string(),
+                msg: mint.clone().into_binary().unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: "indexer".to_string(),
+                    msg: mint.into_binary().unwrap(),
+                    funds: vec![],
+                },
+                HOOK_REPLY_ID,
+            ),
+        ]
+    );
+
+    // so do transfers, once the hook is removed it is no longer notified
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::RemoveHook {
+                contract: "indexer".to_string(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::TransferNft {
+                recipient: "venus".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let transfer = Cw721HookMsg::Transfer {
+        token_id: "1".to_string(),
+        previous_owner: ADMIN.to_string(),
+        owner: "venus".to_string(),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "game".to_string(),
+            msg: transfer.into_binary().unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // failed notifications from ignoring hooks are swallowed
+    let res = contract
+        .reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: HOOK_REPLY_ID,
+                result: SubMsgResult::Err("out of gas".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "hook_failed")
+            .add_attribute("error", "out of gas")
+    );
+    let err = contract
+        .reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 42,
+                result: SubMsgResult::Err("out of gas".to_string()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnknownReplyId { id: 42 });
+}
+
+#[test]
+fn helper_mint_funds() {
+    let helper = crate::helpers::Cw721Contract::<Empty, Empty>(
+        Addr::unchecked("collection"),
+        Default::default(),
+        Default::default(),
+    );
+
+    let funds = crate::helpers::mint_funds(Uint128::from(PUBLIC_PRICE), 3);
+    assert_eq!(funds, coins(3 * u128::from(PUBLIC_PRICE), "usei"));
+
+    let msg = helper.mint_public(3, None::<Empty>, funds.clone()).unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "collection".to_string(),
+            msg: to_json_binary(&ExecuteMsg::<Extension, Empty>::MintPublic {
+                quantity: 3,
+                extension: None,
+            })
+            .unwrap(),
+            funds,
+        })
+    );
+
+    // team mints are free
+    let msg = helper.mint_team(1, None::<Empty>, None).unwrap();
+    let CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) = msg else {
+        panic!("expected a wasm execute");
+    };
+    assert!(funds.is_empty());
+}
+
+#[test]
+fn pending_changes_in_range() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let admin = mock_info(ADMIN, &[]);
+    for address in ["treasury", "vault", "safe"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                admin.clone(),
+                ExecuteMsg::SetWithdrawAddress {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+    }
+    let pending_ids = |start_after: Option<&str>,
+                       start_at: Option<&str>,
+                       end_before: Option<&str>,
+                       order: Option<OrderBy>| {
+        let pending: PendingChangesResponse = from_json(
+            contract
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::PendingChanges {
+                        start_after: start_after.map(String::from),
+                        limit: None,
+                        start_at: start_at.map(String::from),
+                        end_before: end_before.map(String::from),
+                        order,
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        pending
+            .changes
+            .into_iter()
+            .map(|change| change.id)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(pending_ids(None, None, None, None), vec![1, 2, 3]);
+    assert_eq!(pending_ids(Some("1"), None, None, None), vec![2, 3]);
+    assert_eq!(pending_ids(None, Some("2"), None, None), vec![2, 3]);
+    assert_eq!(pending_ids(None, None, Some("3"), None), vec![1, 2]);
+
+    // descending pages walk back from their cursor
+    assert_eq!(
+        pending_ids(None, None, None, Some(OrderBy::Desc)),
+        vec![3, 2, 1]
+    );
+    assert_eq!(
+        pending_ids(Some("3"), None, None, Some(OrderBy::Desc)),
+        vec![2, 1]
+    );
+    assert_eq!(
+        pending_ids(None, Some("2"), Some("1"), Some(OrderBy::Desc)),
+        vec![2]
+    );
+}
+
+#[test]
+fn timelocked_changes() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let admin = mock_info(ADMIN, &[]);
+    let random = mock_info("random", &[]);
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetWithdrawAddress {
+                address: "treasury".to_string(),
+            },
+        )
+        .unwrap();
+    let effective_at = mock_env().block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY);
+    assert_eq!(
+        res.attributes[2..],
+        [
+            ("change_id", "1".to_string()),
+            ("effective_at", effective_at.seconds().to_string())
+        ]
+    );
+
+    // pending changes can be monitored
+    let pending: PendingChangesResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PendingChanges {
+                    start_after: None,
+                    limit: None,
+                    start_at: None,
+                    end_before: None,
+                    order: None,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        pending.changes,
+        vec![PendingChange {
+            id: 1,
+            change: TimelockedChange::WithdrawAddress {
+                address: Some("treasury".to_string())
+            },
+            effective_at,
+        }]
+    );
+
+    // not before the delay has passed
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::ApplyChange { id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ChangeNotEffective {
+            id: 1,
+            effective_at
+        }
+    );
+
+    // only the owner can cancel
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random.clone(),
+            ExecuteMsg::CancelChange { id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::CancelChange { id: 1 },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            random.clone(),
+            ExecuteMsg::ApplyChange { id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::PendingChangeNotFound { id: 1 });
+    let withdraw_address = contract
+        .withdraw_address
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(withdraw_address, ADMIN);
+
+    // a longer delay applies immediately
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::SetTimelockDelay {
+                seconds: 2 * DEFAULT_TIMELOCK_DELAY,
+            },
+        )
+        .unwrap();
+    let delay: TimelockDelayResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::TimelockDelay {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(delay.seconds, 2 * DEFAULT_TIMELOCK_DELAY);
+
+    // a shorter one waits for the current delay
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::SetTimelockDelay { seconds: 60 },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            after_timelock(),
+            random.clone(),
+            ExecuteMsg::ApplyChange { id: 2 },
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ChangeNotEffective { id: 2, .. }
+    ));
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(2 * DEFAULT_TIMELOCK_DELAY);
+    contract
+        .execute(
+            deps.as_mut(),
+            env,
+            random,
+            ExecuteMsg::ApplyChange { id: 2 },
+        )
+        .unwrap();
+    assert_eq!(contract.timelock_delay(deps.as_ref().storage).unwrap(), 60);
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp};
 use cw_ownable::OwnershipError;
 use thiserror::Error;
 
@@ -13,6 +13,9 @@ pub enum ContractError {
     #[error(transparent)]
     Version(#[from] cw2::VersionError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("token_id already claimed")]
     Claimed {},
 
@@ -46,6 +49,69 @@ pub enum ContractError {
     #[error("Not on allowlist")]
     NotOnAllowlist {},
 
+    #[error("Holder claims are closed")]
+    HolderClaimClosed {},
+
+    #[error("Source token {token_id} is not owned by the sender")]
+    NotSourceTokenOwner { token_id: String },
+
+    #[error("Source token {token_id} was already claimed with")]
+    SourceTokenClaimed { token_id: String },
+
+    #[error("{collection} is not a mint pass collection")]
+    UnknownMintPass { collection: String },
+
+    #[error("No raffle was set")]
+    RaffleNotSet {},
+
+    #[error("Raffle already has entries")]
+    RaffleStarted {},
+
+    #[error("Raffle is closed")]
+    RaffleClosed {},
+
+    #[error("Raffle was not drawn yet")]
+    RaffleNotDrawn {},
+
+    #[error("Raffle can't be drawn before height {draw_height}")]
+    RaffleDrawPending { draw_height: u64 },
+
+    #[error("Raffle draw height must be in the future")]
+    InvalidDrawHeight {},
+
+    #[error("Entropy commitment must be a hex encoded sha256")]
+    InvalidEntropyCommitment {},
+
+    #[error("Raffle refund deadline must be after the draw height")]
+    InvalidRefundDeadline {},
+
+    #[error("Raffle can't be drawn from its refund deadline {refund_deadline}")]
+    RaffleExpired { refund_deadline: u64 },
+
+    #[error("Already entered the raffle")]
+    AlreadyEntered {},
+
+    #[error("Entropy doesn't match the commitment")]
+    InvalidEntropy {},
+
+    #[error("No raffle win to claim")]
+    NotRaffleWinner {},
+
+    #[error("No raffle payment to refund")]
+    NoRaffleRefund {},
+
+    #[error("Funds are escrowed for the raffle")]
+    RaffleEscrow {},
+
+    #[error("Contracts can't mint publicly")]
+    ContractMinter {},
+
+    #[error("Public mints of this block reached {max_mints_per_block}")]
+    BlockMintLimit { max_mints_per_block: u64 },
+
+    #[error("Wait {blocks_left} more blocks before minting again")]
+    MintCooldown { blocks_left: u64 },
+
     #[error("Max supply reached")]
     MaxSupplyReached {},
 
@@ -58,6 +124,45 @@ pub enum ContractError {
     #[error("Burning is disabled")]
     BurnDisabled {},
 
+    #[error("Max per mint of the {phase} sale must be positive")]
+    ZeroMintLimit { phase: String },
+
     #[error("Invalid collection size")]
     InvalidCollectionSize {},
+
+    #[error("Reserved supply exceeds what's left of the collection")]
+    InvalidReservedSupply {},
+
+    #[error("Invalid base token uri: {base_token_uri}")]
+    InvalidBaseTokenUri { base_token_uri: String },
+
+    #[error("Token {token_id} has a token uri other than {expected}")]
+    TokenUriMismatch { token_id: String, expected: String },
+
+    #[error("Token is not transferable")]
+    NotTransferable {},
+
+    #[error("Operator not allowed: {operator}")]
+    OperatorNotAllowed { operator: String },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Cannot migrate from {from} to {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("Pending change not found: {id}")]
+    PendingChangeNotFound { id: u64 },
+
+    #[error("Change {id} cannot be applied before {effective_at}")]
+    ChangeNotEffective { id: u64, effective_at: Timestamp },
+
+    #[error("Change is not supported by this contract")]
+    UnsupportedChange {},
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
@@ -1,21 +1,39 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
 use cw_ownable::OwnershipError;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use cosmwasm_std::{
-    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    Storage, Uint128,
+    from_json, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, QuerierWrapper, Reply, Response, StdResult, Storage,
+    SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
 
 use cw721::{ContractInfoResponse, Cw721Execute, Cw721ReceiveMsg, Expiration};
+use cw_storage_plus::{Bound, Map, PrimaryKey};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, SaleConfigResponse};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+use crate::helpers;
+use crate::hook::{Cw721HookMsg, HOOK_REPLY_ID};
+use crate::msg::{
+    ExecuteMsg, HolderClaimConfigResponse, HookErrorPolicy, InstantiateMsg, MintPassConfig,
+    MintProtectionResponse, OperatorFilterMode, PendingChange, RaffleConfig, RaffleDrawResponse,
+    RaffleStatus, SaleConfigResponse, TimelockedChange,
+};
+use crate::raffle;
+use crate::receiver::Cw721BatchReceiveMsg;
+use crate::state::{Approval, Cw721Contract, MintBatch, TokenInfo};
+use crate::validation::{validate_base_token_uri, validate_collection_size, validate_sale_config};
+use crate::MINT_DENOM;
+
+/// Reply id of mint pass burns, passes that fail to burn stay locked in the contract
+pub const MINT_PASS_BURN_REPLY_ID: u64 = 2;
 
 impl<'a, T, C, E, Q> Cw721Contract<'a, T, C, E, Q>
 where
-    T: Serialize + DeserializeOwned + Clone,
+    T: Serialize + DeserializeOwned + Clone + Default,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
@@ -27,6 +45,9 @@ where
         info: MessageInfo,
         msg: InstantiateMsg,
     ) -> Result<Response<C>, ContractError> {
+        validate_base_token_uri(&msg.base_token_uri)?;
+        validate_collection_size(msg.collection_size)?;
+
         let contract_info = ContractInfoResponse {
             name: msg.name,
             symbol: msg.symbol,
@@ -59,6 +80,7 @@ where
             allowlist_sale_open: false,
             og_sale_open: false,
         };
+        validate_sale_config(&sale_config)?;
         self.sale_config.save(deps.storage, &sale_config)?;
         Ok(Response::default())
     }
@@ -75,7 +97,8 @@ where
             ExecuteMsg::MintTeam {
                 quantity,
                 extension,
-            } => self.mint_team(deps, info, quantity, extension),
+                transferable,
+            } => self.mint_team(deps, info, quantity, extension, transferable),
             ExecuteMsg::MintOg {
                 quantity,
                 extension,
@@ -87,7 +110,11 @@ where
             ExecuteMsg::MintPublic {
                 quantity,
                 extension,
-            } => self.mint_public(deps, info, quantity, extension),
+            } => self.mint_public(deps, env, info, quantity, extension),
+            ExecuteMsg::HolderClaim {
+                source_token_ids,
+                extension,
+            } => self.holder_claim(deps, info, source_token_ids, extension),
             ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -96,6 +123,14 @@ where
             ExecuteMsg::Revoke { spender, token_id } => {
                 self.revoke(deps, env, info, spender, token_id)
             }
+            ExecuteMsg::BatchApprove {
+                spender,
+                token_ids,
+                expires,
+            } => self.batch_approve(deps, env, info, spender, token_ids, expires),
+            ExecuteMsg::BatchRevoke { spender, token_ids } => {
+                self.batch_revoke(deps, env, info, spender, token_ids)
+            }
             ExecuteMsg::ApproveAll { operator, expires } => {
                 self.approve_all(deps, env, info, operator, expires)
             }
@@ -109,16 +144,41 @@ where
                 token_id,
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
+            ExecuteMsg::BatchTransferNft {
+                recipient,
+                token_ids,
+            } => self.batch_transfer_nft(deps, env, info, recipient, token_ids),
+            ExecuteMsg::BatchSendNft {
+                contract,
+                token_ids,
+                msg,
+                aggregate,
+            } => self.batch_send_nft(
+                deps,
+                env,
+                info,
+                contract,
+                token_ids,
+                msg,
+                aggregate.unwrap_or(false),
+            ),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
             ExecuteMsg::UpdateOwnership(action) => Self::update_ownership(deps, env, info, action),
             ExecuteMsg::Extension { msg: _ } => Ok(Response::default()),
             ExecuteMsg::SetWithdrawAddress { address } => {
-                self.set_withdraw_address(deps.storage, deps.api, &info.sender, address)
+                self.set_withdraw_address(deps.storage, deps.api, &env.block, &info.sender, address)
             }
             ExecuteMsg::RemoveWithdrawAddress {} => {
-                self.remove_withdraw_address(deps.storage, &info.sender)
+                self.remove_withdraw_address(deps.storage, &env.block, &info.sender)
+            }
+            ExecuteMsg::SetTimelockDelay { seconds } => {
+                self.set_timelock_delay(deps.storage, &env.block, &info.sender, seconds)
+            }
+            ExecuteMsg::CancelChange { id } => self.cancel_change(deps.storage, &info.sender, id),
+            ExecuteMsg::ApplyChange { id } => self.apply_change(deps.storage, &env.block, id),
+            ExecuteMsg::WithdrawFunds { amount } => {
+                self.withdraw_funds(deps.as_ref(), &env, &amount)
             }
-            ExecuteMsg::WithdrawFunds { amount } => self.withdraw_funds(deps.storage, &amount),
             ExecuteMsg::SetBaseTokenUri { base_token_uri } => {
                 self.set_base_token_uri(deps, &info.sender, base_token_uri)
             }
@@ -139,14 +199,29 @@ where
                 max_per_allowlist,
                 max_per_public,
             ),
-            ExecuteMsg::AddToOgList { addresses } => {
-                self.add_to_og_list(deps, &info.sender, addresses)
-            }
+            ExecuteMsg::UpdateSaleConfig {
+                og_price,
+                allowlist_price,
+                public_price,
+                max_per_og,
+                max_per_allowlist,
+                max_per_public,
+            } => self.update_sale_config(
+                deps,
+                &info.sender,
+                og_price,
+                allowlist_price,
+                public_price,
+                max_per_og,
+                max_per_allowlist,
+                max_per_public,
+            ),
+            ExecuteMsg::AddToOgList { entries } => self.add_to_og_list(deps, &info.sender, entries),
             ExecuteMsg::RemoveFromOgList { addresses } => {
                 self.remove_from_og_list(deps, &info.sender, addresses)
             }
-            ExecuteMsg::AddToAllowlist { addresses } => {
-                self.add_to_allowlist(deps, &info.sender, addresses)
+            ExecuteMsg::AddToAllowlist { entries } => {
+                self.add_to_allowlist(deps, &info.sender, entries)
             }
             ExecuteMsg::RemoveFromAllowlist { addresses } => {
                 self.remove_from_allowlist(deps, &info.sender, addresses)
@@ -156,16 +231,104 @@ where
                 self.set_allowlist_sale(deps, &info.sender, open)
             }
             ExecuteMsg::SetPublicSale { open } => self.set_public_sale(deps, &info.sender, open),
+            ExecuteMsg::SetHolderClaimConfig {
+                source_collection,
+                source_tokens_per_claim,
+                mints_per_claim,
+                open,
+            } => self.set_holder_claim_config(
+                deps,
+                &info.sender,
+                source_collection,
+                source_tokens_per_claim,
+                mints_per_claim,
+                open,
+            ),
+            ExecuteMsg::SetMintProtection {
+                reject_contracts,
+                max_mints_per_block,
+                min_blocks_between_mints,
+            } => self.set_mint_protection(
+                deps,
+                &info.sender,
+                MintProtectionResponse {
+                    reject_contracts,
+                    max_mints_per_block,
+                    min_blocks_between_mints,
+                },
+            ),
+            ExecuteMsg::SetCompactMint { enabled } => {
+                self.set_compact_mint(deps, &info.sender, enabled)
+            }
             ExecuteMsg::SetCollectionSize { collection_size } => {
                 self.set_collection_size(deps, &info.sender, collection_size)
             }
+            ExecuteMsg::SetReservedSupply { reserved_supply } => {
+                self.set_reserved_supply(deps, &info.sender, reserved_supply)
+            }
+            ExecuteMsg::SetTransferable {
+                token_id,
+                transferable,
+            } => self.set_transferable(deps, &info.sender, token_id, transferable),
+            ExecuteMsg::SetOperatorFilter { mode } => {
+                self.set_operator_filter(deps, &info.sender, mode)
+            }
+            ExecuteMsg::AddToOperatorRegistry { addresses } => {
+                self.add_to_operator_registry(deps, &info.sender, addresses)
+            }
+            ExecuteMsg::RemoveFromOperatorRegistry { addresses } => {
+                self.remove_from_operator_registry(deps, &info.sender, addresses)
+            }
+            ExecuteMsg::AddHook {
+                contract,
+                error_policy,
+            } => self.add_hook(
+                deps,
+                &info.sender,
+                contract,
+                error_policy.unwrap_or_default(),
+            ),
+            ExecuteMsg::RemoveHook { contract } => self.remove_hook(deps, &info.sender, contract),
+            ExecuteMsg::ReceiveNft(msg) => self.redeem_mint_pass(deps, info, msg),
+            ExecuteMsg::SetMintPass {
+                collection,
+                mints_per_pass,
+                burn,
+            } => self.set_mint_pass(deps, &info.sender, collection, mints_per_pass, burn),
+            ExecuteMsg::RemoveMintPass { collection } => {
+                self.remove_mint_pass(deps, &info.sender, collection)
+            }
+            ExecuteMsg::SetRaffle {
+                price,
+                winners,
+                entropy_commitment,
+                draw_height,
+                refund_deadline,
+            } => self.set_raffle(
+                deps,
+                &env,
+                &info.sender,
+                price,
+                winners,
+                entropy_commitment,
+                draw_height,
+                refund_deadline,
+            ),
+            ExecuteMsg::EnterRaffle {} => self.enter_raffle(deps, env, info),
+            ExecuteMsg::DrawRaffle { entropy } => {
+                self.draw_raffle(deps, env, &info.sender, entropy)
+            }
+            ExecuteMsg::ClaimRaffleMint { extension } => {
+                self.claim_raffle_mint(deps, info, extension)
+            }
+            ExecuteMsg::ClaimRaffleRefund {} => self.claim_raffle_refund(deps, env, info),
         }
     }
 }
 
 impl<'a, T, C, E, Q> Cw721Contract<'a, T, C, E, Q>
 where
-    T: Serialize + DeserializeOwned + Clone,
+    T: Serialize + DeserializeOwned + Clone + Default,
     C: CustomMsg,
     E: CustomMsg,
     Q: CustomMsg,
@@ -180,6 +343,7 @@ where
         info: MessageInfo,
         quantity: u64,
         extension: T,
+        transferable: Option<bool>,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
@@ -187,33 +351,36 @@ where
             return Err(ContractError::InvalidQuantity {});
         }
 
-        // Make sure number of tokens doesn't exceed collection size
+        // Make sure number of tokens doesn't exceed collection size, less the raffle winners
         let collection_size = self.collection_size.load(deps.storage)?;
         let token_count = self.token_count(deps.storage)?;
-        if token_count + quantity > collection_size {
+        let raffle_reserved = self.raffle_reserved_supply(deps.storage)?;
+        if token_count + quantity + raffle_reserved > collection_size {
             return Err(ContractError::MaxSupplyReached {});
         }
 
         // Create tokens based on quantity
-        for i in 0..quantity {
-            // Create the token
-            let token_id = (token_count + i).to_string();
-            let token = TokenInfo {
-                owner: info.sender.clone(),
-                approvals: vec![],
-                extension: extension.clone(),
-            };
-            self.tokens
-                .update(deps.storage, &token_id, |old| match old {
-                    Some(_) => Err(ContractError::Claimed {}),
-                    None => Ok(token),
-                })?;
-        }
+        self.create_tokens(
+            deps.storage,
+            &info.sender,
+            token_count,
+            quantity,
+            extension,
+            transferable,
+        )?;
 
         // Update the total minted count
         self.increment_tokens(deps.storage, quantity)?;
+        self.increment_balance(deps.storage, &info.sender, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, quantity)?;
+
+        // Team mints come out of the reservation first
+        let reserved_supply = self.reserved_supply(deps.storage)?;
+        self.reserved_supply
+            .save(deps.storage, &reserved_supply.saturating_sub(quantity))?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_attribute("action", "mint")
             .add_attribute("minter", info.sender)
             .add_attribute("quantity", quantity.to_string()))
@@ -238,23 +405,20 @@ where
         }
 
         // Verify if the sender is on the og list
-        let is_og = self
+        let allocation = self
             .og_list
             .may_load(deps.storage, &info.sender)?
-            .unwrap_or(false);
-        if !is_og {
-            return Err(ContractError::NotOnOgList {});
-        }
+            .ok_or(ContractError::NotOnOgList {})?;
 
-        // Make sure quantity doesn't exceed max per og
-        if quantity > sale_config.max_per_og {
+        // Make sure quantity doesn't exceed max per og or what's left of the allocation
+        if quantity > sale_config.max_per_og || quantity > allocation {
             return Err(ContractError::MaxMintReached {});
         }
 
-        // Make sure number of tokens doesn't exceed collection size
-        let collection_size = self.collection_size.load(deps.storage)?;
+        // Make sure number of tokens doesn't exceed collection size, less the team reservation
+        let public_supply = self.public_supply(deps.storage)?;
         let token_count = self.token_count(deps.storage)?;
-        if token_count + quantity > collection_size {
+        if token_count + quantity > public_supply {
             return Err(ContractError::MaxSupplyReached {});
         }
 
@@ -263,33 +427,36 @@ where
         let sent_amount = info
             .funds
             .iter()
-            .find(|coin| coin.denom == "usei")
+            .find(|coin| coin.denom == MINT_DENOM)
             .map_or(Uint128::zero(), |coin| coin.amount);
         if sent_amount < total_price {
             return Err(ContractError::InsufficientFunds {});
         }
 
         // Create tokens based on quantity
-        for i in 0..quantity {
-            let token_id = (token_count + i).to_string();
-            let token = TokenInfo {
-                owner: info.sender.clone(),
-                approvals: vec![],
-                extension: extension.clone(),
-            };
-            self.tokens
-                .update(deps.storage, &token_id, |old| match old {
-                    Some(_) => Err(ContractError::Claimed {}),
-                    None => Ok(token),
-                })?;
-        }
+        self.create_tokens(
+            deps.storage,
+            &info.sender,
+            token_count,
+            quantity,
+            extension,
+            None,
+        )?;
 
         // Update the total minted count
         self.increment_tokens(deps.storage, quantity)?;
-        // Remove from og list
-        self.og_list.remove(deps.storage, &info.sender);
+        self.increment_balance(deps.storage, &info.sender, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, quantity)?;
+        self.use_allocation(
+            deps.storage,
+            &self.og_list,
+            &info.sender,
+            allocation,
+            quantity,
+        )?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_attribute("action", "mint_og")
             .add_attribute("minter", info.sender)
             .add_attribute("quantity", quantity.to_string()))
@@ -313,28 +480,26 @@ where
             return Err(ContractError::InvalidQuantity {});
         }
 
-        // Verify if the sender is an og or on allowlist
-        let is_og = self
-            .og_list
-            .may_load(deps.storage, &info.sender)?
-            .unwrap_or(false);
-        let is_allowed = self
-            .allowlist
-            .may_load(deps.storage, &info.sender)?
-            .unwrap_or(false);
-        if !is_og && !is_allowed {
-            return Err(ContractError::NotOnAllowlist {});
-        }
+        // Verify if the sender is an og or on allowlist, og allocations are used first
+        let (list, allocation) = match self.og_list.may_load(deps.storage, &info.sender)? {
+            Some(allocation) => (&self.og_list, allocation),
+            None => (
+                &self.allowlist,
+                self.allowlist
+                    .may_load(deps.storage, &info.sender)?
+                    .ok_or(ContractError::NotOnAllowlist {})?,
+            ),
+        };
 
-        // Make sure quantity doesn't exceed max per allowlist
-        if quantity > sale_config.max_per_allowlist {
+        // Make sure quantity doesn't exceed max per allowlist or what's left of the allocation
+        if quantity > sale_config.max_per_allowlist || quantity > allocation {
             return Err(ContractError::MaxMintReached {});
         }
 
-        // Make sure number of tokens doesn't exceed collection size
-        let collection_size = self.collection_size.load(deps.storage)?;
+        // Make sure number of tokens doesn't exceed collection size, less the team reservation
+        let public_supply = self.public_supply(deps.storage)?;
         let token_count = self.token_count(deps.storage)?;
-        if token_count + quantity > collection_size {
+        if token_count + quantity > public_supply {
             return Err(ContractError::MaxSupplyReached {});
         }
 
@@ -343,37 +508,30 @@ where
         let sent_amount = info
             .funds
             .iter()
-            .find(|coin| coin.denom == "usei")
+            .find(|coin| coin.denom == MINT_DENOM)
             .map_or(Uint128::zero(), |coin| coin.amount);
         if sent_amount < total_price {
             return Err(ContractError::InsufficientFunds {});
         }
 
         // Create tokens based on quantity
-        for i in 0..quantity {
-            let token_id = (token_count + i).to_string();
-            let token = TokenInfo {
-                owner: info.sender.clone(),
-                approvals: vec![],
-                extension: extension.clone(),
-            };
-            self.tokens
-                .update(deps.storage, &token_id, |old| match old {
-                    Some(_) => Err(ContractError::Claimed {}),
-                    None => Ok(token),
-                })?;
-        }
+        self.create_tokens(
+            deps.storage,
+            &info.sender,
+            token_count,
+            quantity,
+            extension,
+            None,
+        )?;
 
         // Update the total minted count
         self.increment_tokens(deps.storage, quantity)?;
-        // Remove from oglist or allowlist
-        if is_og {
-            self.og_list.remove(deps.storage, &info.sender)
-        } else {
-            self.allowlist.remove(deps.storage, &info.sender);
-        }
+        self.increment_balance(deps.storage, &info.sender, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, quantity)?;
+        self.use_allocation(deps.storage, list, &info.sender, allocation, quantity)?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_attribute("action", "mint_allowlist")
             .add_attribute("minter", info.sender)
             .add_attribute("quantity", quantity.to_string()))
@@ -382,6 +540,7 @@ where
     pub fn mint_public(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         quantity: u64,
         extension: T,
@@ -401,10 +560,10 @@ where
             return Err(ContractError::MaxMintReached {});
         }
 
-        // Make sure number of tokens doesn't exceed collection size
-        let collection_size = self.collection_size.load(deps.storage)?;
+        // Make sure number of tokens doesn't exceed collection size, less the team reservation
+        let public_supply = self.public_supply(deps.storage)?;
         let token_count = self.token_count(deps.storage)?;
-        if token_count + quantity > collection_size {
+        if token_count + quantity > public_supply {
             return Err(ContractError::MaxSupplyReached {});
         }
 
@@ -413,37 +572,614 @@ where
         let sent_amount = info
             .funds
             .iter()
-            .find(|coin| coin.denom == "usei")
+            .find(|coin| coin.denom == MINT_DENOM)
             .map_or(Uint128::zero(), |coin| coin.amount);
         if sent_amount < total_price {
             return Err(ContractError::InsufficientFunds {});
         }
 
+        self.check_mint_protection(deps.storage, &deps.querier, &env, &info.sender, quantity)?;
+
         // Create tokens based on quantity
-        for i in 0..quantity {
-            // Create the token
-            let token_id = (token_count + i).to_string();
-            let token = TokenInfo {
-                owner: info.sender.clone(),
-                approvals: vec![],
-                extension: extension.clone(),
-            };
-            self.tokens
-                .update(deps.storage, &token_id, |old| match old {
-                    Some(_) => Err(ContractError::Claimed {}),
-                    None => Ok(token),
-                })?;
-        }
+        self.create_tokens(
+            deps.storage,
+            &info.sender,
+            token_count,
+            quantity,
+            extension,
+            None,
+        )?;
 
         // Update the total minted count
         self.increment_tokens(deps.storage, quantity)?;
+        self.increment_balance(deps.storage, &info.sender, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, quantity)?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_attribute("action", "mint")
             .add_attribute("minter", info.sender)
             .add_attribute("quantity", quantity.to_string()))
     }
 
+    /// Checks the public mint protections, recording the mint for the ones
+    /// that throttle by block
+    pub fn check_mint_protection(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        env: &Env,
+        sender: &Addr,
+        quantity: u64,
+    ) -> Result<(), ContractError> {
+        let protection = self.mint_protection.may_load(storage)?.unwrap_or_default();
+        let height = env.block.height;
+
+        // Only contracts have contract info
+        if protection.reject_contracts && querier.query_wasm_contract_info(sender).is_ok() {
+            return Err(ContractError::ContractMinter {});
+        }
+
+        if let Some(min_blocks) = protection.min_blocks_between_mints {
+            if let Some(last_height) = self.last_mint_heights.may_load(storage, sender)? {
+                let next_height = last_height.saturating_add(min_blocks);
+                if height < next_height {
+                    return Err(ContractError::MintCooldown {
+                        blocks_left: next_height - height,
+                    });
+                }
+            }
+        }
+
+        let block_mints = match self.block_mints.may_load(storage)? {
+            Some((last_height, minted)) if last_height == height => minted + quantity,
+            _ => quantity,
+        };
+        if let Some(max_mints_per_block) = protection.max_mints_per_block {
+            if block_mints > max_mints_per_block {
+                return Err(ContractError::BlockMintLimit {
+                    max_mints_per_block,
+                });
+            }
+        }
+
+        if protection.min_blocks_between_mints.is_some() {
+            self.last_mint_heights.save(storage, sender, &height)?;
+        }
+        if protection.max_mints_per_block.is_some() {
+            self.block_mints.save(storage, &(height, block_mints))?;
+        }
+        Ok(())
+    }
+
+    pub fn holder_claim(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        source_token_ids: Vec<String>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        let config = self
+            .holder_claim_config
+            .may_load(deps.storage)?
+            .filter(|config| config.open)
+            .ok_or(ContractError::HolderClaimClosed {})?;
+
+        // Source tokens are claimed with in whole groups
+        let num_source_tokens = source_token_ids.len() as u64;
+        if num_source_tokens == 0 || num_source_tokens % config.source_tokens_per_claim != 0 {
+            return Err(ContractError::InvalidQuantity {});
+        }
+        let quantity = (num_source_tokens / config.source_tokens_per_claim)
+            .checked_mul(config.mints_per_claim)
+            .ok_or(ContractError::InvalidQuantity {})?;
+
+        // Make sure number of tokens doesn't exceed collection size, less the team reservation
+        let public_supply = self.public_supply(deps.storage)?;
+        let token_count = self.token_count(deps.storage)?;
+        if token_count
+            .checked_add(quantity)
+            .map_or(true, |count| count > public_supply)
+        {
+            return Err(ContractError::MaxSupplyReached {});
+        }
+
+        // Verify the sender owns every source token and hasn't claimed with it
+        let source = helpers::Cw721Contract::<Empty, Empty>(
+            config.source_collection.clone(),
+            PhantomData,
+            PhantomData,
+        );
+        let mut seen = BTreeSet::new();
+        for token_id in &source_token_ids {
+            let claimed = self
+                .claimed_source_tokens
+                .has(deps.storage, (&config.source_collection, token_id));
+            if claimed || !seen.insert(token_id) {
+                return Err(ContractError::SourceTokenClaimed {
+                    token_id: token_id.clone(),
+                });
+            }
+            let owner = source.owner_of(&deps.querier, token_id, false)?.owner;
+            if owner != info.sender {
+                return Err(ContractError::NotSourceTokenOwner {
+                    token_id: token_id.clone(),
+                });
+            }
+        }
+        for token_id in &source_token_ids {
+            self.claimed_source_tokens.save(
+                deps.storage,
+                (&config.source_collection, token_id),
+                &info.sender,
+            )?;
+        }
+
+        // Create tokens based on quantity
+        self.create_tokens(
+            deps.storage,
+            &info.sender,
+            token_count,
+            quantity,
+            extension,
+            None,
+        )?;
+
+        // Update the total minted count
+        self.increment_tokens(deps.storage, quantity)?;
+        self.increment_balance(deps.storage, &info.sender, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, quantity)?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "holder_claim")
+            .add_attribute("minter", info.sender)
+            .add_attribute("quantity", quantity.to_string())
+            .add_attribute("source_token_ids", source_token_ids.join(",")))
+    }
+
+    /// Mints tokens to whoever sent a pass of a registered collection, `info.sender`
+    /// is the pass collection
+    pub fn redeem_mint_pass(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response<C>, ContractError> {
+        let config = self
+            .mint_passes
+            .may_load(deps.storage, &info.sender)?
+            .ok_or_else(|| ContractError::UnknownMintPass {
+                collection: info.sender.to_string(),
+            })?;
+        let minter = deps.api.addr_validate(&msg.sender)?;
+        // Passes sent without a msg mint tokens with the default extension
+        let extension: T = if msg.msg.is_empty() {
+            T::default()
+        } else {
+            from_json(&msg.msg)?
+        };
+        let quantity = config.mints_per_pass;
+
+        // Make sure number of tokens doesn't exceed collection size, less the team reservation
+        let public_supply = self.public_supply(deps.storage)?;
+        let token_count = self.token_count(deps.storage)?;
+        if token_count + quantity > public_supply {
+            return Err(ContractError::MaxSupplyReached {});
+        }
+
+        // Create tokens based on quantity
+        self.create_tokens(
+            deps.storage,
+            &minter,
+            token_count,
+            quantity,
+            extension,
+            None,
+        )?;
+
+        // Update the total minted count
+        self.increment_tokens(deps.storage, quantity)?;
+        self.increment_balance(deps.storage, &minter, quantity)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &minter, token_count, quantity)?;
+
+        let mut res = Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "redeem_mint_pass")
+            .add_attribute("minter", minter)
+            .add_attribute("quantity", quantity.to_string())
+            .add_attribute("pass_collection", info.sender.to_string())
+            .add_attribute("pass_token_id", msg.token_id.clone());
+        // The pass was transferred to this contract before the callback, it stays
+        // locked here if the pass collection can't burn it
+        if config.burn {
+            let burn = ExecuteMsg::<Empty, Empty>::Burn {
+                token_id: msg.token_id,
+            };
+            let burn = WasmMsg::Execute {
+                contract_addr: info.sender.into_string(),
+                msg: to_json_binary(&burn)?,
+                funds: vec![],
+            };
+            res = res.add_submessage(SubMsg::reply_on_error(burn, MINT_PASS_BURN_REPLY_ID));
+        }
+        Ok(res)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_raffle(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        sender: &Addr,
+        price: Uint128,
+        winners: u64,
+        entropy_commitment: String,
+        draw_height: u64,
+        refund_deadline: u64,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        if self.raffle_entries.has(deps.storage, 0) {
+            return Err(ContractError::RaffleStarted {});
+        }
+        if winners == 0 {
+            return Err(ContractError::InvalidQuantity {});
+        }
+        if draw_height <= env.block.height {
+            return Err(ContractError::InvalidDrawHeight {});
+        }
+        if refund_deadline <= draw_height {
+            return Err(ContractError::InvalidRefundDeadline {});
+        }
+        if !raffle::is_commitment(&entropy_commitment) {
+            return Err(ContractError::InvalidEntropyCommitment {});
+        }
+
+        let config = RaffleConfig {
+            price,
+            winners,
+            entropy_commitment: entropy_commitment.to_lowercase(),
+            draw_height,
+            refund_deadline,
+        };
+        self.raffle_config.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_raffle")
+            .add_attribute("price", price)
+            .add_attribute("winners", winners.to_string())
+            .add_attribute("entropy_commitment", config.entropy_commitment)
+            .add_attribute("draw_height", draw_height.to_string())
+            .add_attribute("refund_deadline", refund_deadline.to_string()))
+    }
+
+    pub fn enter_raffle(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let config = self
+            .raffle_config
+            .may_load(deps.storage)?
+            .ok_or(ContractError::RaffleNotSet {})?;
+        if env.block.height >= config.draw_height || self.raffle_draw.exists(deps.storage) {
+            return Err(ContractError::RaffleClosed {});
+        }
+        if self.raffle_entrants.has(deps.storage, &info.sender) {
+            return Err(ContractError::AlreadyEntered {});
+        }
+
+        // Make sure enough funds are sent
+        let sent_amount = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == MINT_DENOM)
+            .map_or(Uint128::zero(), |coin| coin.amount);
+        if sent_amount < config.price {
+            return Err(ContractError::InsufficientFunds {});
+        }
+
+        let index = self.raffle_num_entries(deps.storage)?;
+        self.raffle_entries
+            .save(deps.storage, index, &info.sender)?;
+        self.raffle_entrants
+            .save(deps.storage, &info.sender, &RaffleStatus::Entered)?;
+        let entries_hash = self
+            .raffle_entries_hash
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        self.raffle_entries_hash.save(
+            deps.storage,
+            &raffle::chain_entry(&entries_hash, info.sender.as_str()),
+        )?;
+        let escrow = self.raffle_escrow(deps.storage)? + config.price;
+        self.raffle_escrow.save(deps.storage, &escrow)?;
+
+        let mut res = Response::new();
+        let change = sent_amount - config.price;
+        if !change.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin::new(change.u128(), MINT_DENOM)],
+            });
+        }
+        Ok(res
+            .add_attribute("action", "enter_raffle")
+            .add_attribute("entrant", info.sender)
+            .add_attribute("entry", index.to_string()))
+    }
+
+    /// Draws as many winners as configured, entries and the remaining supply allow,
+    /// reserving supply for each winner
+    pub fn draw_raffle(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        sender: &Addr,
+        entropy: String,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        let config = self
+            .raffle_config
+            .may_load(deps.storage)?
+            .ok_or(ContractError::RaffleNotSet {})?;
+        if self.raffle_draw.exists(deps.storage) {
+            return Err(ContractError::RaffleClosed {});
+        }
+        if env.block.height < config.draw_height {
+            return Err(ContractError::RaffleDrawPending {
+                draw_height: config.draw_height,
+            });
+        }
+        // Past the deadline entrants may already be getting refunded
+        if env.block.height >= config.refund_deadline {
+            return Err(ContractError::RaffleExpired {
+                refund_deadline: config.refund_deadline,
+            });
+        }
+        if raffle::entropy_commitment(&entropy) != config.entropy_commitment {
+            return Err(ContractError::InvalidEntropy {});
+        }
+
+        let num_entries = self.raffle_num_entries(deps.storage)?;
+        let supply = self
+            .public_supply(deps.storage)?
+            .saturating_sub(self.token_count(deps.storage)?);
+        let entries_hash = self
+            .raffle_entries_hash
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        let seed = raffle::draw_seed(
+            &entropy,
+            &entries_hash,
+            &env.block.chain_id,
+            config.draw_height,
+        );
+        let winners = raffle::draw_winners(&seed, num_entries, config.winners.min(supply));
+        for index in &winners {
+            let winner = self.raffle_entries.load(deps.storage, *index)?;
+            self.raffle_entrants
+                .save(deps.storage, &winner, &RaffleStatus::Won)?;
+        }
+        // Winners' tokens are kept out of other mints until claimed or refunded
+        let raffle_reserved = self.raffle_reserved_supply(deps.storage)? + winners.len() as u64;
+        self.raffle_reserved_supply
+            .save(deps.storage, &raffle_reserved)?;
+
+        let draw = RaffleDrawResponse {
+            seed: raffle::to_hex(&seed),
+            entropy,
+            entries_hash: raffle::to_hex(&entries_hash),
+            num_entries,
+            num_winners: winners.len() as u64,
+            height: env.block.height,
+            time: env.block.time,
+        };
+        self.raffle_draw.save(deps.storage, &draw)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "draw_raffle")
+            .add_attribute("seed", draw.seed)
+            .add_attribute("num_entries", num_entries.to_string())
+            .add_attribute("num_winners", draw.num_winners.to_string()))
+    }
+
+    pub fn claim_raffle_mint(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        let config = self
+            .raffle_config
+            .may_load(deps.storage)?
+            .ok_or(ContractError::RaffleNotSet {})?;
+        let status = self.raffle_entrants.may_load(deps.storage, &info.sender)?;
+        if status != Some(RaffleStatus::Won) {
+            return Err(ContractError::NotRaffleWinner {});
+        }
+
+        // Winners mint from the supply reserved for them at the draw
+        let raffle_reserved = self.raffle_reserved_supply(deps.storage)?;
+        let public_supply = self.public_supply(deps.storage)?;
+        let token_count = self.token_count(deps.storage)?;
+        if token_count >= public_supply + raffle_reserved {
+            return Err(ContractError::MaxSupplyReached {});
+        }
+        self.raffle_reserved_supply
+            .save(deps.storage, &raffle_reserved.saturating_sub(1))?;
+
+        let token_id = token_count.to_string();
+        self.create_tokens(deps.storage, &info.sender, token_count, 1, extension, None)?;
+        self.increment_tokens(deps.storage, 1)?;
+        self.increment_balance(deps.storage, &info.sender, 1)?;
+        let hooks = self.mint_hook_msgs(deps.storage, &info.sender, token_count, 1)?;
+
+        // The escrowed payment now pays for the token
+        self.raffle_entrants
+            .save(deps.storage, &info.sender, &RaffleStatus::Claimed)?;
+        let escrow = self.raffle_escrow(deps.storage)? - config.price;
+        self.raffle_escrow.save(deps.storage, &escrow)?;
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "claim_raffle_mint")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn claim_raffle_refund(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let config = self
+            .raffle_config
+            .may_load(deps.storage)?
+            .ok_or(ContractError::RaffleNotSet {})?;
+        if !self.raffle_draw.exists(deps.storage) && env.block.height < config.refund_deadline {
+            return Err(ContractError::RaffleNotDrawn {});
+        }
+        let refundable = self.raffle_refundable(deps.as_ref(), &env, &info.sender)?;
+        if refundable.is_zero() {
+            return Err(ContractError::NoRaffleRefund {});
+        }
+
+        // A refunded winner gives up the supply reserved for them
+        let status = self.raffle_entrants.load(deps.storage, &info.sender)?;
+        if status == RaffleStatus::Won {
+            let raffle_reserved = self.raffle_reserved_supply(deps.storage)?;
+            self.raffle_reserved_supply
+                .save(deps.storage, &raffle_reserved.saturating_sub(1))?;
+        }
+        self.raffle_entrants
+            .save(deps.storage, &info.sender, &RaffleStatus::Refunded)?;
+        let escrow = self.raffle_escrow(deps.storage)? - refundable;
+        self.raffle_escrow.save(deps.storage, &escrow)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin::new(refundable.u128(), MINT_DENOM)],
+            })
+            .add_attribute("action", "claim_raffle_refund")
+            .add_attribute("entrant", info.sender)
+            .add_attribute("amount", refundable))
+    }
+
+    pub fn batch_transfer_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_can_transfer_batch(deps.as_ref(), &env, &info, &recipient, &token_ids)?;
+
+        // Transfer tokens
+        let mut hooks = vec![];
+        for token_id in &token_ids {
+            hooks.extend(self._transfer_nft(deps.branch(), &env, &info, &recipient, token_id)?);
+        }
+
+        Ok(Response::new()
+            .add_submessages(hooks)
+            .add_attribute("action", "batch_transfer_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_ids", token_ids.join(",")))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_send_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+        aggregate: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_can_transfer_batch(deps.as_ref(), &env, &info, &contract, &token_ids)?;
+
+        // Transfer tokens
+        let mut hooks = vec![];
+        for token_id in &token_ids {
+            hooks.extend(self._transfer_nft(deps.branch(), &env, &info, &contract, token_id)?);
+        }
+
+        // Notify the receiving contract once, or once per token
+        let messages = if aggregate {
+            let send = Cw721BatchReceiveMsg {
+                sender: info.sender.to_string(),
+                token_ids: token_ids.clone(),
+                msg,
+            };
+            vec![send.into_cosmos_msg(contract.clone())?]
+        } else {
+            token_ids
+                .iter()
+                .map(|token_id| {
+                    let send = Cw721ReceiveMsg {
+                        sender: info.sender.to_string(),
+                        token_id: token_id.clone(),
+                        msg: msg.clone(),
+                    };
+                    send.into_cosmos_msg(contract.clone())
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        };
+
+        Ok(Response::new()
+            .add_messages(messages)
+            .add_submessages(hooks)
+            .add_attribute("action", "batch_send_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", contract)
+            .add_attribute("token_ids", token_ids.join(",")))
+    }
+
+    pub fn batch_approve(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        let changed =
+            self._batch_update_approvals(deps, &env, &info, &spender, &token_ids, true, expires)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "batch_approve")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_ids", changed.join(",")))
+    }
+
+    pub fn batch_revoke(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let changed =
+            self._batch_update_approvals(deps, &env, &info, &spender, &token_ids, false, None)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "batch_revoke")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_ids", changed.join(",")))
+    }
+
     pub fn update_ownership(
         deps: DepsMut,
         env: Env,
@@ -458,41 +1194,192 @@ where
         &self,
         storage: &mut dyn Storage,
         api: &dyn Api,
+        block: &BlockInfo,
         sender: &Addr,
         address: String,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(storage, sender)?;
         api.addr_validate(&address)?;
-        self.withdraw_address.save(storage, &address)?;
+
+        let change = TimelockedChange::WithdrawAddress {
+            address: Some(address.clone()),
+        };
+        let pending = self.queue_change(storage, block, change)?;
         Ok(Response::new()
             .add_attribute("action", "set_withdraw_address")
-            .add_attribute("address", address))
+            .add_attribute("address", address)
+            .add_attribute("change_id", pending.id.to_string())
+            .add_attribute("effective_at", pending.effective_at.seconds().to_string()))
     }
 
     pub fn remove_withdraw_address(
         &self,
         storage: &mut dyn Storage,
+        block: &BlockInfo,
         sender: &Addr,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(storage, sender)?;
         let address = self.withdraw_address.may_load(storage)?;
         match address {
             Some(address) => {
-                self.withdraw_address.remove(storage);
+                let change = TimelockedChange::WithdrawAddress { address: None };
+                let pending = self.queue_change(storage, block, change)?;
                 Ok(Response::new()
                     .add_attribute("action", "remove_withdraw_address")
-                    .add_attribute("address", address))
+                    .add_attribute("address", address)
+                    .add_attribute("change_id", pending.id.to_string())
+                    .add_attribute("effective_at", pending.effective_at.seconds().to_string()))
             }
             None => Err(ContractError::NoWithdrawAddress {}),
         }
     }
 
-    pub fn withdraw_funds(
+    pub fn set_timelock_delay(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+        seconds: u64,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(storage, sender)?;
+
+        let res = Response::new()
+            .add_attribute("action", "set_timelock_delay")
+            .add_attribute("seconds", seconds.to_string());
+        // a longer delay only makes changes safer, so it doesn't wait
+        if seconds >= self.timelock_delay(storage)? {
+            self.timelock_delay.save(storage, &seconds)?;
+            return Ok(res);
+        }
+
+        let pending =
+            self.queue_change(storage, block, TimelockedChange::TimelockDelay { seconds })?;
+        Ok(res
+            .add_attribute("change_id", pending.id.to_string())
+            .add_attribute("effective_at", pending.effective_at.seconds().to_string()))
+    }
+
+    /// Queues `change` until the timelock delay has passed
+    pub fn queue_change(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        change: TimelockedChange,
+    ) -> Result<PendingChange, ContractError> {
+        let id = self.last_change_id.may_load(storage)?.unwrap_or_default() + 1;
+        self.last_change_id.save(storage, &id)?;
+
+        let pending = PendingChange {
+            id,
+            change,
+            effective_at: block.time.plus_seconds(self.timelock_delay(storage)?),
+        };
+        self.pending_changes.save(storage, id, &pending)?;
+        Ok(pending)
+    }
+
+    /// Queues an extension message, applied through `Cw721Extension::apply_change`
+    /// once the timelock delay has passed
+    pub fn queue_extension_change(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        msg: &E,
+    ) -> Result<PendingChange, ContractError> {
+        let change = TimelockedChange::Extension {
+            msg: to_json_binary(msg)?,
+        };
+        self.queue_change(storage, block, change)
+    }
+
+    /// Pending change `id`, if it can be applied at `block`
+    pub fn effective_change(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        id: u64,
+    ) -> Result<PendingChange, ContractError> {
+        let pending = self
+            .pending_changes
+            .may_load(storage, id)?
+            .ok_or(ContractError::PendingChangeNotFound { id })?;
+        if block.time < pending.effective_at {
+            return Err(ContractError::ChangeNotEffective {
+                id,
+                effective_at: pending.effective_at,
+            });
+        }
+        Ok(pending)
+    }
+
+    pub fn cancel_change(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(storage, sender)?;
+        if !self.pending_changes.has(storage, id) {
+            return Err(ContractError::PendingChangeNotFound { id });
+        }
+
+        self.pending_changes.remove(storage, id);
+        Ok(Response::new()
+            .add_attribute("action", "cancel_change")
+            .add_attribute("change_id", id.to_string()))
+    }
+
+    pub fn apply_change(
         &self,
         storage: &mut dyn Storage,
+        block: &BlockInfo,
+        id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let pending = self.effective_change(storage, block, id)?;
+
+        let res = Response::new()
+            .add_attribute("action", "apply_change")
+            .add_attribute("change_id", id.to_string());
+        let res = match pending.change {
+            TimelockedChange::WithdrawAddress {
+                address: Some(address),
+            } => {
+                self.withdraw_address.save(storage, &address)?;
+                res.add_attribute("withdraw_address", address)
+            }
+            TimelockedChange::WithdrawAddress { address: None } => {
+                self.withdraw_address.remove(storage);
+                res.add_attribute("withdraw_address", "none")
+            }
+            TimelockedChange::TimelockDelay { seconds } => {
+                self.timelock_delay.save(storage, &seconds)?;
+                res.add_attribute("timelock_delay", seconds.to_string())
+            }
+            // only an extension can queue these, see `execute_with`
+            TimelockedChange::Extension { .. } => return Err(ContractError::UnsupportedChange {}),
+        };
+        self.pending_changes.remove(storage, id);
+        Ok(res)
+    }
+
+    pub fn withdraw_funds(
+        &self,
+        deps: Deps,
+        env: &Env,
         amount: &Coin,
     ) -> Result<Response<C>, ContractError> {
-        let address = self.withdraw_address.may_load(storage)?;
+        // Raffle payments stay until they're claimed or refunded
+        let escrow = self.raffle_escrow(deps.storage)?;
+        if amount.denom == MINT_DENOM && !escrow.is_zero() {
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, MINT_DENOM)?;
+            if amount.amount > balance.amount.saturating_sub(escrow) {
+                return Err(ContractError::RaffleEscrow {});
+            }
+        }
+
+        let address = self.withdraw_address.may_load(deps.storage)?;
         match address {
             Some(address) => {
                 let msg = BankMsg::Send {
@@ -516,6 +1403,7 @@ where
         base_token_uri: String,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(deps.storage, sender)?;
+        validate_base_token_uri(&base_token_uri)?;
 
         self.base_token_uri.save(deps.storage, &base_token_uri)?;
 
@@ -545,6 +1433,7 @@ where
         sale_config.max_per_og = max_per_og;
         sale_config.max_per_allowlist = max_per_allowlist;
         sale_config.max_per_public = max_per_public;
+        validate_sale_config(&sale_config)?;
         self.sale_config.save(deps.storage, &sale_config)?;
 
         Ok(Response::new()
@@ -557,20 +1446,126 @@ where
             .add_attribute("max_per_public", max_per_public.to_string()))
     }
 
+    /// Patches the provided fields, reporting each changed field as
+    /// `<field>_before` and `<field>_after` attributes
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_sale_config(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        og_price: Option<Uint128>,
+        allowlist_price: Option<Uint128>,
+        public_price: Option<Uint128>,
+        max_per_og: Option<u64>,
+        max_per_allowlist: Option<u64>,
+        max_per_public: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+
+        let before = self.sale_config.load(deps.storage)?;
+        let after = SaleConfigResponse {
+            og_price: og_price.unwrap_or(before.og_price),
+            allowlist_price: allowlist_price.unwrap_or(before.allowlist_price),
+            public_price: public_price.unwrap_or(before.public_price),
+            max_per_og: max_per_og.unwrap_or(before.max_per_og),
+            max_per_allowlist: max_per_allowlist.unwrap_or(before.max_per_allowlist),
+            max_per_public: max_per_public.unwrap_or(before.max_per_public),
+            ..before.clone()
+        };
+
+        validate_sale_config(&after)?;
+        self.sale_config.save(deps.storage, &after)?;
+
+        let fields = [
+            (
+                "og_price",
+                before.og_price.to_string(),
+                after.og_price.to_string(),
+            ),
+            (
+                "allowlist_price",
+                before.allowlist_price.to_string(),
+                after.allowlist_price.to_string(),
+            ),
+            (
+                "public_price",
+                before.public_price.to_string(),
+                after.public_price.to_string(),
+            ),
+            (
+                "max_per_og",
+                before.max_per_og.to_string(),
+                after.max_per_og.to_string(),
+            ),
+            (
+                "max_per_allowlist",
+                before.max_per_allowlist.to_string(),
+                after.max_per_allowlist.to_string(),
+            ),
+            (
+                "max_per_public",
+                before.max_per_public.to_string(),
+                after.max_per_public.to_string(),
+            ),
+        ];
+        let mut res = Response::new().add_attribute("action", "update_sale_config");
+        for (field, before, after) in fields {
+            if before != after {
+                res = res
+                    .add_attribute(format!("{field}_before"), before)
+                    .add_attribute(format!("{field}_after"), after);
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn set_holder_claim_config(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        source_collection: String,
+        source_tokens_per_claim: u64,
+        mints_per_claim: u64,
+        open: bool,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        // A claim can't mint more than the whole collection
+        let collection_size = self.collection_size.load(deps.storage)?;
+        if source_tokens_per_claim == 0 || mints_per_claim == 0 || mints_per_claim > collection_size
+        {
+            return Err(ContractError::InvalidQuantity {});
+        }
+
+        let config = HolderClaimConfigResponse {
+            source_collection: deps.api.addr_validate(&source_collection)?,
+            source_tokens_per_claim,
+            mints_per_claim,
+            open,
+        };
+        self.holder_claim_config.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_holder_claim_config")
+            .add_attribute("source_collection", config.source_collection)
+            .add_attribute(
+                "source_tokens_per_claim",
+                source_tokens_per_claim.to_string(),
+            )
+            .add_attribute("mints_per_claim", mints_per_claim.to_string())
+            .add_attribute("open", open.to_string()))
+    }
+
     pub fn add_to_og_list(
         &self,
         deps: DepsMut,
         sender: &Addr,
-        addresses: Vec<String>,
+        entries: Vec<(String, u64)>,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(deps.storage, sender)?;
-        for address in addresses.clone() {
-            let og_addr = deps.api.addr_validate(&address)?;
-            self.og_list.save(deps.storage, &og_addr, &true)?;
-        }
+        self.save_allocations(deps, &self.og_list, &entries)?;
         Ok(Response::new()
             .add_attribute("action", "add_to_og_list")
-            .add_attribute("num_addresses", addresses.len().to_string()))
+            .add_attribute("num_addresses", entries.len().to_string()))
     }
 
     pub fn remove_from_og_list(
@@ -593,16 +1588,51 @@ where
         &self,
         deps: DepsMut,
         sender: &Addr,
-        addresses: Vec<String>,
+        entries: Vec<(String, u64)>,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(deps.storage, sender)?;
-        for address in addresses.clone() {
-            let allowlist_addr = deps.api.addr_validate(&address)?;
-            self.allowlist.save(deps.storage, &allowlist_addr, &true)?;
-        }
+        self.save_allocations(deps, &self.allowlist, &entries)?;
         Ok(Response::new()
             .add_attribute("action", "add_to_allowlist")
-            .add_attribute("num_addresses", addresses.len().to_string()))
+            .add_attribute("num_addresses", entries.len().to_string()))
+    }
+
+    /// Sets the allocation of every entry, replacing what was left of previous ones
+    fn save_allocations(
+        &self,
+        deps: DepsMut,
+        list: &Map<&Addr, u64>,
+        entries: &[(String, u64)],
+    ) -> Result<(), ContractError> {
+        let entries = entries
+            .iter()
+            .map(|(address, allocation)| {
+                if *allocation == 0 {
+                    return Err(ContractError::InvalidQuantity {});
+                }
+                Ok((deps.api.addr_validate(address)?, *allocation))
+            })
+            .collect::<Result<Vec<_>, ContractError>>()?;
+        for (address, allocation) in entries {
+            list.save(deps.storage, &address, &allocation)?;
+        }
+        Ok(())
+    }
+
+    /// Takes `quantity` off the allocation of `address`, which leaves the list once used up
+    fn use_allocation(
+        &self,
+        storage: &mut dyn Storage,
+        list: &Map<&Addr, u64>,
+        address: &Addr,
+        allocation: u64,
+        quantity: u64,
+    ) -> StdResult<()> {
+        match allocation - quantity {
+            0 => list.remove(storage, address),
+            remaining => list.save(storage, address, &remaining)?,
+        }
+        Ok(())
     }
 
     pub fn remove_from_allowlist(
@@ -672,6 +1702,47 @@ where
             .add_attribute("open", open.to_string()))
     }
 
+    pub fn set_mint_protection(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        protection: MintProtectionResponse,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        if protection.max_mints_per_block == Some(0) {
+            return Err(ContractError::InvalidQuantity {});
+        }
+
+        self.mint_protection.save(deps.storage, &protection)?;
+
+        let optional = |value: Option<u64>| value.map_or("none".to_string(), |v| v.to_string());
+        Ok(Response::new()
+            .add_attribute("action", "set_mint_protection")
+            .add_attribute("reject_contracts", protection.reject_contracts.to_string())
+            .add_attribute(
+                "max_mints_per_block",
+                optional(protection.max_mints_per_block),
+            )
+            .add_attribute(
+                "min_blocks_between_mints",
+                optional(protection.min_blocks_between_mints),
+            ))
+    }
+
+    pub fn set_compact_mint(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        enabled: bool,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        self.compact_mint.save(deps.storage, &enabled)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_compact_mint")
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
     pub fn set_collection_size(
         &self,
         deps: DepsMut,
@@ -679,10 +1750,13 @@ where
         collection_size: u64,
     ) -> Result<Response<C>, ContractError> {
         cw_ownable::assert_owner(deps.storage, sender)?;
+        validate_collection_size(collection_size)?;
 
-        // Collection size must be greater than or equal to token count
+        // Collection size must cover the tokens minted and the team and raffle reservations
         let token_count = self.token_count(deps.storage)?;
-        if collection_size < token_count {
+        let reserved =
+            self.reserved_supply(deps.storage)? + self.raffle_reserved_supply(deps.storage)?;
+        if collection_size < token_count + reserved {
             return Err(ContractError::InvalidCollectionSize {});
         }
 
@@ -692,6 +1766,195 @@ where
             .add_attribute("action", "set_collection_size")
             .add_attribute("collection_size", collection_size.to_string()))
     }
+
+    pub fn set_reserved_supply(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        reserved_supply: u64,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+
+        // The reservation must fit in what's left of the collection, next to raffle winners
+        let collection_size = self.collection_size.load(deps.storage)?;
+        let token_count = self.token_count(deps.storage)?;
+        let raffle_reserved = self.raffle_reserved_supply(deps.storage)?;
+        if token_count + reserved_supply + raffle_reserved > collection_size {
+            return Err(ContractError::InvalidReservedSupply {});
+        }
+
+        self.reserved_supply.save(deps.storage, &reserved_supply)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_reserved_supply")
+            .add_attribute("reserved_supply", reserved_supply.to_string()))
+    }
+
+    pub fn set_transferable(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        token_id: Option<String>,
+        transferable: bool,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+
+        let res = Response::new().add_attribute("action", "set_transferable");
+        let res = match token_id {
+            Some(token_id) => {
+                // Make sure the token exists
+                self.load_token(deps.storage, &token_id)?;
+                self.token_transferable
+                    .save(deps.storage, &token_id, &transferable)?;
+                res.add_attribute("token_id", token_id)
+            }
+            None => {
+                self.transferable.save(deps.storage, &transferable)?;
+                res
+            }
+        };
+
+        Ok(res.add_attribute("transferable", transferable.to_string()))
+    }
+
+    pub fn set_operator_filter(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        mode: OperatorFilterMode,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+
+        self.operator_filter.save(deps.storage, &mode)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_operator_filter")
+            .add_attribute("mode", format!("{mode:?}").to_lowercase()))
+    }
+
+    pub fn add_to_operator_registry(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        addresses: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        for address in addresses.clone() {
+            let operator_addr = deps.api.addr_validate(&address)?;
+            self.operator_registry
+                .save(deps.storage, &operator_addr, &true)?;
+        }
+        Ok(Response::new()
+            .add_attribute("action", "add_to_operator_registry")
+            .add_attribute("num_addresses", addresses.len().to_string()))
+    }
+
+    pub fn remove_from_operator_registry(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        addresses: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        for address in addresses.clone() {
+            let operator_addr = deps.api.addr_validate(&address)?;
+            self.operator_registry.remove(deps.storage, &operator_addr);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "remove_from_operator_registry")
+            .add_attribute("num_addresses", addresses.len().to_string()))
+    }
+
+    pub fn add_hook(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        contract: String,
+        error_policy: HookErrorPolicy,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        let contract_addr = deps.api.addr_validate(&contract)?;
+        self.hooks
+            .save(deps.storage, &contract_addr, &error_policy)?;
+        Ok(Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("contract", contract)
+            .add_attribute("error_policy", format!("{error_policy:?}").to_lowercase()))
+    }
+
+    pub fn remove_hook(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        contract: String,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        let contract_addr = deps.api.addr_validate(&contract)?;
+        self.hooks.remove(deps.storage, &contract_addr);
+        Ok(Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("contract", contract))
+    }
+
+    pub fn set_mint_pass(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        collection: String,
+        mints_per_pass: u64,
+        burn: bool,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        if mints_per_pass == 0 {
+            return Err(ContractError::InvalidQuantity {});
+        }
+        let collection_addr = deps.api.addr_validate(&collection)?;
+        let config = MintPassConfig {
+            mints_per_pass,
+            burn,
+        };
+        self.mint_passes
+            .save(deps.storage, &collection_addr, &config)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_mint_pass")
+            .add_attribute("collection", collection)
+            .add_attribute("mints_per_pass", mints_per_pass.to_string())
+            .add_attribute("burn", burn.to_string()))
+    }
+
+    pub fn remove_mint_pass(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        collection: String,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, sender)?;
+        let collection_addr = deps.api.addr_validate(&collection)?;
+        self.mint_passes.remove(deps.storage, &collection_addr);
+        Ok(Response::new()
+            .add_attribute("action", "remove_mint_pass")
+            .add_attribute("collection", collection))
+    }
+
+    /// Handles replies from hook notifications sent with `HookErrorPolicy::Ignore`
+    /// and from mint pass burns
+    pub fn reply(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        msg: Reply,
+    ) -> Result<Response<C>, ContractError> {
+        match (msg.id, msg.result) {
+            (HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+                .add_attribute("action", "hook_failed")
+                .add_attribute("error", err)),
+            (HOOK_REPLY_ID, SubMsgResult::Ok(_)) => Ok(Response::new()),
+            (MINT_PASS_BURN_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+                .add_attribute("action", "mint_pass_locked")
+                .add_attribute("error", err)),
+            (id, _) => Err(ContractError::UnknownReplyId { id }),
+        }
+    }
 }
 
 impl<'a, T, C, E, Q> Cw721Execute<T, C> for Cw721Contract<'a, T, C, E, Q>
@@ -711,9 +1974,10 @@ where
         recipient: String,
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
-        self._transfer_nft(deps, &env, &info, &recipient, &token_id)?;
+        let hooks = self._transfer_nft(deps, &env, &info, &recipient, &token_id)?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_attribute("action", "transfer_nft")
             .add_attribute("sender", info.sender)
             .add_attribute("recipient", recipient)
@@ -730,7 +1994,7 @@ where
         msg: Binary,
     ) -> Result<Response<C>, ContractError> {
         // Transfer token
-        self._transfer_nft(deps, &env, &info, &contract, &token_id)?;
+        let hooks = self._transfer_nft(deps, &env, &info, &contract, &token_id)?;
 
         let send = Cw721ReceiveMsg {
             sender: info.sender.to_string(),
@@ -741,6 +2005,7 @@ where
         // Send message
         Ok(Response::new()
             .add_message(send.into_cosmos_msg(contract.clone())?)
+            .add_submessages(hooks)
             .add_attribute("action", "send_nft")
             .add_attribute("sender", info.sender)
             .add_attribute("recipient", contract)
@@ -790,6 +2055,11 @@ where
         operator: String,
         expires: Option<Expiration>,
     ) -> Result<Response<C>, ContractError> {
+        // operators can't be granted on non-transferable collections
+        if !self.is_collection_transferable(deps.storage)? {
+            return Err(ContractError::NotTransferable {});
+        }
+
         // reject expired data as invalid
         let expires = expires.unwrap_or_default();
         if expires.is_expired(&env.block) {
@@ -798,6 +2068,7 @@ where
 
         // set the operator for us
         let operator_addr = deps.api.addr_validate(&operator)?;
+        self.check_operator_allowed(deps.storage, &operator_addr)?;
         self.operators
             .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
 
@@ -843,6 +2114,7 @@ where
     E: CustomMsg,
     Q: CustomMsg,
 {
+    /// Moves a token to `recipient`, returning the hook notifications for the transfer
     pub fn _transfer_nft(
         &self,
         deps: DepsMut,
@@ -850,15 +2122,120 @@ where
         info: &MessageInfo,
         recipient: &str,
         token_id: &str,
-    ) -> Result<TokenInfo<T>, ContractError> {
+    ) -> Result<Vec<SubMsg<C>>, ContractError> {
+        self.unpack_token(deps.storage, token_id)?;
         let mut token = self.tokens.load(deps.storage, token_id)?;
         // ensure we have permissions
-        self.check_can_send(deps.as_ref(), env, info, &token)?;
+        self.check_can_transfer(deps.as_ref(), env, info, token_id, &token)?;
         // set owner and remove existing approvals
-        token.owner = deps.api.addr_validate(recipient)?;
+        let recipient = deps.api.addr_validate(recipient)?;
+        self.decrement_balance(deps.storage, &token.owner)?;
+        self.increment_balance(deps.storage, &recipient, 1)?;
+        let hook = Cw721HookMsg::Transfer {
+            token_id: token_id.to_string(),
+            previous_owner: token.owner.to_string(),
+            owner: recipient.to_string(),
+        };
+        token.owner = recipient;
         token.approvals = vec![];
         self.tokens.save(deps.storage, token_id, &token)?;
-        Ok(token)
+        Ok(self.hook_msgs(deps.storage, hook)?)
+    }
+
+    /// Notifications of `msg` for every registered hook contract
+    pub fn hook_msgs(&self, storage: &dyn Storage, msg: Cw721HookMsg) -> StdResult<Vec<SubMsg<C>>> {
+        self.hooks
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (contract, error_policy) = item?;
+                msg.clone().into_sub_msg(contract, &error_policy)
+            })
+            .collect()
+    }
+
+    /// Creates `quantity` tokens owned by `owner` from id `first_token`, as a
+    /// single mint batch in compact mode
+    fn create_tokens(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        first_token: u64,
+        quantity: u64,
+        extension: T,
+        transferable: Option<bool>,
+    ) -> Result<(), ContractError> {
+        if quantity > 1 && self.compact_mint.may_load(storage)?.unwrap_or_default() {
+            // Ids of the batch must all be free, like with separate tokens
+            let end = first_token + quantity;
+            let taken = self
+                .tokens
+                .idx
+                .number
+                .keys_raw(
+                    storage,
+                    Some(Bound::InclusiveRaw(
+                        (first_token, String::new()).joined_key(),
+                    )),
+                    Some(Bound::ExclusiveRaw((end, String::new()).joined_key())),
+                    Order::Ascending,
+                )
+                .next()
+                .is_some();
+            if taken
+                || self
+                    .mint_batch_of(storage, &first_token.to_string())?
+                    .is_some()
+            {
+                return Err(ContractError::Claimed {});
+            }
+
+            let batch = MintBatch {
+                owner: owner.clone(),
+                end,
+                extension,
+                transferable,
+            };
+            self.mint_batches.save(storage, first_token, &batch)?;
+            return Ok(());
+        }
+
+        for i in 0..quantity {
+            let token_id = (first_token + i).to_string();
+            let token = TokenInfo {
+                owner: owner.clone(),
+                approvals: vec![],
+                extension: extension.clone(),
+            };
+            self.tokens.update(storage, &token_id, |old| match old {
+                Some(_) => Err(ContractError::Claimed {}),
+                None => Ok(token),
+            })?;
+            if let Some(transferable) = transferable {
+                self.token_transferable
+                    .save(storage, &token_id, &transferable)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Hook notifications for `quantity` tokens minted from `first_token`
+    fn mint_hook_msgs(
+        &self,
+        storage: &dyn Storage,
+        owner: &Addr,
+        first_token: u64,
+        quantity: u64,
+    ) -> StdResult<Vec<SubMsg<C>>> {
+        let token_ids = (first_token..first_token + quantity)
+            .map(|token_id| token_id.to_string())
+            .collect();
+        self.hook_msgs(
+            storage,
+            Cw721HookMsg::Mint {
+                token_ids,
+                owner: owner.to_string(),
+            },
+        )
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -873,31 +2250,115 @@ where
         add: bool,
         expires: Option<Expiration>,
     ) -> Result<TokenInfo<T>, ContractError> {
+        self.unpack_token(deps.storage, token_id)?;
         let mut token = self.tokens.load(deps.storage, token_id)?;
+        self._apply_approval(
+            deps.as_ref(),
+            env,
+            info,
+            spender,
+            token_id,
+            &mut token,
+            add,
+            expires,
+        )?;
+        self.tokens.save(deps.storage, token_id, &token)?;
+
+        Ok(token)
+    }
+
+    /// Updates approvals for many tokens, checking all of them before saving any.
+    /// Returns the ids of the tokens whose approvals changed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn _batch_update_approvals(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        spender: &str,
+        token_ids: &[String],
+        // if add == false, remove. if add == true, remove then set with this expiration
+        add: bool,
+        expires: Option<Expiration>,
+    ) -> Result<Vec<String>, ContractError> {
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidQuantity {});
+        }
+
+        let mut updated = vec![];
+        for token_id in token_ids {
+            let mut token = self.load_token(deps.storage, token_id)?;
+            let changed = self._apply_approval(
+                deps.as_ref(),
+                env,
+                info,
+                spender,
+                token_id,
+                &mut token,
+                add,
+                expires,
+            )?;
+            if changed {
+                updated.push((token_id.clone(), token));
+            }
+        }
+
+        let mut changed_ids = vec![];
+        for (token_id, token) in updated {
+            self.unpack_token(deps.storage, &token_id)?;
+            self.tokens.save(deps.storage, &token_id, &token)?;
+            changed_ids.push(token_id);
+        }
+
+        Ok(changed_ids)
+    }
+
+    /// Updates the approvals of a loaded token, returns true if they changed
+    #[allow(clippy::too_many_arguments)]
+    fn _apply_approval(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        spender: &str,
+        token_id: &str,
+        token: &mut TokenInfo<T>,
+        add: bool,
+        expires: Option<Expiration>,
+    ) -> Result<bool, ContractError> {
         // ensure we have permissions
-        self.check_can_approve(deps.as_ref(), env, info, &token)?;
+        self.check_can_approve(deps, env, info, token)?;
 
         // update the approval list (remove any for the same spender before adding)
         let spender_addr = deps.api.addr_validate(spender)?;
+        let previous = token
+            .approvals
+            .iter()
+            .find(|apr| apr.spender == spender_addr)
+            .cloned();
         token.approvals.retain(|apr| apr.spender != spender_addr);
 
         // only difference between approve and revoke
         if add {
+            // approvals are only useful for tokens that can be transferred
+            if !self.is_transferable(deps.storage, token_id)? {
+                return Err(ContractError::NotTransferable {});
+            }
             // reject expired data as invalid
             let expires = expires.unwrap_or_default();
             if expires.is_expired(&env.block) {
                 return Err(ContractError::Expired {});
             }
+            self.check_operator_allowed(deps.storage, &spender_addr)?;
             let approval = Approval {
                 spender: spender_addr,
                 expires,
             };
-            token.approvals.push(approval);
+            token.approvals.push(approval.clone());
+            return Ok(previous != Some(approval));
         }
 
-        self.tokens.save(deps.storage, token_id, &token)?;
-
-        Ok(token)
+        Ok(previous.is_some())
     }
 
     /// returns true iff the sender can execute approve or reject on the contract
@@ -928,6 +2389,44 @@ where
         }
     }
 
+    /// returns true iff the sender can transfer ownership of the token and the token
+    /// is transferable
+    pub fn check_can_transfer(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: &str,
+        token: &TokenInfo<T>,
+    ) -> Result<(), ContractError> {
+        self.check_can_send(deps, env, info, token)?;
+        if !self.is_transferable(deps.storage, token_id)? {
+            return Err(ContractError::NotTransferable {});
+        }
+        Ok(())
+    }
+
+    /// checks every token of a batch before any of them is moved, so a failing
+    /// token never leaves the batch partially applied
+    pub fn check_can_transfer_batch(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        recipient: &str,
+        token_ids: &[String],
+    ) -> Result<(), ContractError> {
+        if token_ids.is_empty() {
+            return Err(ContractError::InvalidQuantity {});
+        }
+        deps.api.addr_validate(recipient)?;
+        for token_id in token_ids {
+            let token = self.load_token(deps.storage, token_id)?;
+            self.check_can_transfer(deps, env, info, token_id, &token)?;
+        }
+        Ok(())
+    }
+
     /// returns true iff the sender can transfer ownership of the token
     pub fn check_can_send(
         &self,
@@ -941,16 +2440,16 @@ where
             return Ok(());
         }
 
-        // any non-expired token approval can send
+        // any non-expired token approval can send, if the operator filter allows it
         if token
             .approvals
             .iter()
             .any(|apr| apr.spender == info.sender && !apr.is_expired(&env.block))
         {
-            return Ok(());
+            return self.check_operator_allowed(deps.storage, &info.sender);
         }
 
-        // operator can send
+        // operator can send, if the operator filter allows it
         let op = self
             .operators
             .may_load(deps.storage, (&token.owner, &info.sender))?;
@@ -959,10 +2458,30 @@ where
                 if ex.is_expired(&env.block) {
                     Err(ContractError::Ownership(OwnershipError::NotOwner))
                 } else {
-                    Ok(())
+                    self.check_operator_allowed(deps.storage, &info.sender)
                 }
             }
             None => Err(ContractError::Ownership(OwnershipError::NotOwner)),
         }
     }
+
+    /// returns an error if the operator filter doesn't allow the spender or operator
+    pub fn check_operator_allowed(
+        &self,
+        storage: &dyn Storage,
+        operator: &Addr,
+    ) -> Result<(), ContractError> {
+        let registered = self.operator_registry.has(storage, operator);
+        let allowed = match self.operator_filter(storage)? {
+            OperatorFilterMode::Disabled => true,
+            OperatorFilterMode::Allowlist => registered,
+            OperatorFilterMode::Blocklist => !registered,
+        };
+        if !allowed {
+            return Err(ContractError::OperatorNotAllowed {
+                operator: operator.to_string(),
+            });
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,156 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{
+    from_json, Binary, CustomMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, TimelockedChange};
+use crate::state::Cw721Contract;
+
+/// Custom logic plugged into `Cw721Contract` through `execute_with` and
+/// `query_with`. It handles `ExecuteMsg::Extension` and `QueryMsg::Extension`,
+/// and is called around every token transfer and send.
+pub trait Cw721Extension<C, E, Q> {
+    type Error: From<ContractError>;
+
+    fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: E,
+    ) -> Result<Response<C>, Self::Error>;
+
+    fn query(&self, deps: Deps, env: Env, msg: Q) -> StdResult<Binary>;
+
+    /// Called before a token moves to `recipient`, an error aborts the transfer
+    fn before_transfer(
+        &self,
+        _deps: DepsMut,
+        _env: &Env,
+        _token_id: &str,
+        _recipient: &str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called once a token has moved to `recipient`
+    fn after_transfer(
+        &self,
+        _deps: DepsMut,
+        _env: &Env,
+        _token_id: &str,
+        _recipient: &str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Applies a `TimelockedChange::Extension` the extension queued, once the
+    /// timelock has passed
+    fn apply_change(
+        &self,
+        _deps: DepsMut,
+        _env: &Env,
+        _msg: E,
+    ) -> Result<Response<C>, Self::Error> {
+        Err(ContractError::UnsupportedChange {}.into())
+    }
+}
+
+impl<'a, T, C, E, Q> Cw721Contract<'a, T, C, E, Q>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+    C: CustomMsg,
+    E: CustomMsg,
+    Q: CustomMsg,
+{
+    /// execute, with `ExecuteMsg::Extension` and transfer hooks handled by `extension`
+    pub fn execute_with<X>(
+        &self,
+        extension: &X,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<T, E>,
+    ) -> Result<Response<C>, X::Error>
+    where
+        X: Cw721Extension<C, E, Q>,
+        E: DeserializeOwned,
+    {
+        let transfers = transfers(&msg);
+        for (token_id, recipient) in &transfers {
+            extension.before_transfer(deps.branch(), &env, token_id, recipient)?;
+        }
+
+        let res = match msg {
+            ExecuteMsg::Extension { msg } => {
+                extension.execute(deps.branch(), env.clone(), info, msg)?
+            }
+            ExecuteMsg::ApplyChange { id } => {
+                match self.effective_change(deps.storage, &env.block, id)?.change {
+                    TimelockedChange::Extension { msg } => {
+                        let msg = from_json(msg).map_err(ContractError::from)?;
+                        let res = extension.apply_change(deps.branch(), &env, msg)?;
+                        self.pending_changes.remove(deps.storage, id);
+                        res.add_attribute("change_id", id.to_string())
+                    }
+                    _ => self.apply_change(deps.storage, &env.block, id)?,
+                }
+            }
+            msg => self.execute(deps.branch(), env.clone(), info, msg)?,
+        };
+
+        for (token_id, recipient) in &transfers {
+            extension.after_transfer(deps.branch(), &env, token_id, recipient)?;
+        }
+        Ok(res)
+    }
+
+    /// query, with `QueryMsg::Extension` handled by `extension`
+    pub fn query_with<X>(
+        &self,
+        extension: &X,
+        deps: Deps,
+        env: Env,
+        msg: QueryMsg<Q>,
+    ) -> StdResult<Binary>
+    where
+        X: Cw721Extension<C, E, Q>,
+    {
+        match msg {
+            QueryMsg::Extension { msg } => extension.query(deps, env, msg),
+            msg => self.query(deps, env, msg),
+        }
+    }
+}
+
+/// (token_id, recipient) of every token a message moves
+fn transfers<T, E>(msg: &ExecuteMsg<T, E>) -> Vec<(String, String)> {
+    match msg {
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => vec![(token_id.clone(), recipient.clone())],
+        ExecuteMsg::SendNft {
+            contract, token_id, ..
+        } => vec![(token_id.clone(), contract.clone())],
+        ExecuteMsg::BatchTransferNft {
+            recipient,
+            token_ids,
+        } => token_ids
+            .iter()
+            .map(|token_id| (token_id.clone(), recipient.clone()))
+            .collect(),
+        ExecuteMsg::BatchSendNft {
+            contract,
+            token_ids,
+            ..
+        } => token_ids
+            .iter()
+            .map(|token_id| (token_id.clone(), contract.clone()))
+            .collect(),
+        _ => vec![],
+    }
+}
@@ -2,16 +2,33 @@ use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, CustomMsg, QuerierWrapper, StdResult, WasmMsg, WasmQuery,
+    coins, to_json_binary, Addr, Binary, Coin, CosmosMsg, CustomMsg, Empty, QuerierWrapper,
+    StdResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw721::{
     AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse,
-    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+    Expiration, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
+    TokensResponse,
 };
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use crate::{ExecuteMsg, QueryMsg};
+use crate::msg::{
+    Allocation, AllocationResponse, AllocationsResponse, BalanceResponse, BaseTokenUriResponse,
+    CollectionSizeResponse, CompactMintResponse, Holder, HolderClaimConfigResponse,
+    HoldersResponse, HookErrorPolicy, HookResponse, HooksResponse, MintPass, MintPassesResponse,
+    MintProtectionResponse, NumHoldersResponse, OperatorFilterMode, OperatorFilterResponse,
+    OperatorRegistryResponse, PendingChange, PendingChangesResponse, RaffleDrawResponse,
+    RaffleEntryResponse, RaffleRefundResponse, RaffleResponse, RaffleStatus, RaffleWinner,
+    RaffleWinnersResponse, ReservedSupplyResponse, SaleConfigResponse, SourceTokenClaimResponse,
+    TimelockDelayResponse, TokensWithInfoResponse,
+};
+use crate::{ExecuteMsg, QueryMsg, MINT_DENOM};
+
+/// Funds to send with a mint of `quantity` tokens at `price` each
+pub fn mint_funds(price: Uint128, quantity: u64) -> Vec<Coin> {
+    coins(price.u128() * u128::from(quantity), MINT_DENOM)
+}
 
 #[cw_serde]
 pub struct Cw721Contract<Q: CustomMsg, E: CustomMsg>(
@@ -27,11 +44,19 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
     }
 
     pub fn call<T: Serialize>(&self, msg: ExecuteMsg<T, E>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Serialize>(
+        &self,
+        msg: ExecuteMsg<T, E>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
         let msg = to_json_binary(&msg)?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
-            funds: vec![],
+            funds,
         }
         .into())
     }
@@ -49,6 +74,359 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
         querier.query(&query)
     }
 
+    /*** mints ***/
+
+    /// `transferable` overrides the collection flag for the minted tokens
+    pub fn mint_team<T: Serialize>(
+        &self,
+        quantity: u64,
+        extension: T,
+        transferable: Option<bool>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::MintTeam {
+            quantity,
+            extension,
+            transferable,
+        })
+    }
+
+    /// funds should cover the og price of every token, see `mint_funds`
+    pub fn mint_og<T: Serialize>(
+        &self,
+        quantity: u64,
+        extension: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::MintOg {
+                quantity,
+                extension,
+            },
+            funds,
+        )
+    }
+
+    /// funds should cover the allowlist price of every token, see `mint_funds`
+    pub fn mint_allowlist<T: Serialize>(
+        &self,
+        quantity: u64,
+        extension: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::MintAllowlist {
+                quantity,
+                extension,
+            },
+            funds,
+        )
+    }
+
+    /// funds should cover the public price of every token, see `mint_funds`
+    pub fn mint_public<T: Serialize>(
+        &self,
+        quantity: u64,
+        extension: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        self.call_with_funds(
+            ExecuteMsg::MintPublic {
+                quantity,
+                extension,
+            },
+            funds,
+        )
+    }
+
+    /// funds should cover the raffle price, see `mint_funds`
+    pub fn enter_raffle(&self, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::<Empty, E>::EnterRaffle {}, funds)
+    }
+
+    pub fn claim_raffle_mint<T: Serialize>(&self, extension: T) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimRaffleMint { extension })
+    }
+
+    pub fn holder_claim<T: Serialize>(
+        &self,
+        source_token_ids: Vec<String>,
+        extension: T,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::HolderClaim {
+            source_token_ids,
+            extension,
+        })
+    }
+
+    /*** transfers and approvals ***/
+
+    pub fn batch_transfer_nft(
+        &self,
+        recipient: impl Into<String>,
+        token_ids: Vec<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::BatchTransferNft {
+            recipient: recipient.into(),
+            token_ids,
+        })
+    }
+
+    pub fn batch_send_nft(
+        &self,
+        contract: impl Into<String>,
+        token_ids: Vec<String>,
+        msg: Binary,
+        aggregate: bool,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::BatchSendNft {
+            contract: contract.into(),
+            token_ids,
+            msg,
+            aggregate: Some(aggregate),
+        })
+    }
+
+    pub fn batch_approve(
+        &self,
+        spender: impl Into<String>,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::BatchApprove {
+            spender: spender.into(),
+            token_ids,
+            expires,
+        })
+    }
+
+    pub fn batch_revoke(
+        &self,
+        spender: impl Into<String>,
+        token_ids: Vec<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::BatchRevoke {
+            spender: spender.into(),
+            token_ids,
+        })
+    }
+
+    /*** owner config ***/
+
+    pub fn set_withdraw_address(&self, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetWithdrawAddress {
+            address: address.into(),
+        })
+    }
+
+    pub fn remove_withdraw_address(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveWithdrawAddress {})
+    }
+
+    pub fn set_timelock_delay(&self, seconds: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetTimelockDelay { seconds })
+    }
+
+    pub fn cancel_change(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::CancelChange { id })
+    }
+
+    pub fn apply_change(&self, id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::ApplyChange { id })
+    }
+
+    pub fn withdraw_funds(&self, amount: Coin) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::WithdrawFunds { amount })
+    }
+
+    pub fn set_base_token_uri(&self, base_token_uri: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetBaseTokenUri {
+            base_token_uri: base_token_uri.into(),
+        })
+    }
+
+    pub fn set_sale_config(&self, config: SaleConfigResponse) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetSaleConfig {
+            og_price: config.og_price,
+            allowlist_price: config.allowlist_price,
+            public_price: config.public_price,
+            max_per_og: config.max_per_og,
+            max_per_allowlist: config.max_per_allowlist,
+            max_per_public: config.max_per_public,
+        })
+    }
+
+    /// Fields left as `None` keep their current value
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_sale_config(
+        &self,
+        og_price: Option<Uint128>,
+        allowlist_price: Option<Uint128>,
+        public_price: Option<Uint128>,
+        max_per_og: Option<u64>,
+        max_per_allowlist: Option<u64>,
+        max_per_public: Option<u64>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::UpdateSaleConfig {
+            og_price,
+            allowlist_price,
+            public_price,
+            max_per_og,
+            max_per_allowlist,
+            max_per_public,
+        })
+    }
+
+    /// `entries` are (address, allocation) pairs
+    pub fn add_to_og_list(&self, entries: Vec<(String, u64)>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::AddToOgList { entries })
+    }
+
+    pub fn remove_from_og_list(&self, addresses: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveFromOgList { addresses })
+    }
+
+    /// `entries` are (address, allocation) pairs
+    pub fn add_to_allowlist(&self, entries: Vec<(String, u64)>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::AddToAllowlist { entries })
+    }
+
+    pub fn remove_from_allowlist(&self, addresses: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveFromAllowlist { addresses })
+    }
+
+    pub fn set_og_sale(&self, open: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetOgSale { open })
+    }
+
+    pub fn set_allowlist_sale(&self, open: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetAllowlistSale { open })
+    }
+
+    pub fn set_public_sale(&self, open: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetPublicSale { open })
+    }
+
+    pub fn set_holder_claim_config(
+        &self,
+        source_collection: impl Into<String>,
+        source_tokens_per_claim: u64,
+        mints_per_claim: u64,
+        open: bool,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetHolderClaimConfig {
+            source_collection: source_collection.into(),
+            source_tokens_per_claim,
+            mints_per_claim,
+            open,
+        })
+    }
+
+    pub fn set_mint_pass(
+        &self,
+        collection: impl Into<String>,
+        mints_per_pass: u64,
+        burn: bool,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetMintPass {
+            collection: collection.into(),
+            mints_per_pass,
+            burn,
+        })
+    }
+
+    pub fn remove_mint_pass(&self, collection: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveMintPass {
+            collection: collection.into(),
+        })
+    }
+
+    pub fn set_raffle(
+        &self,
+        price: Uint128,
+        winners: u64,
+        entropy_commitment: impl Into<String>,
+        draw_height: u64,
+        refund_deadline: u64,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetRaffle {
+            price,
+            winners,
+            entropy_commitment: entropy_commitment.into(),
+            draw_height,
+            refund_deadline,
+        })
+    }
+
+    pub fn draw_raffle(&self, entropy: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::DrawRaffle {
+            entropy: entropy.into(),
+        })
+    }
+
+    pub fn claim_raffle_refund(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::ClaimRaffleRefund {})
+    }
+
+    pub fn set_collection_size(&self, collection_size: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetCollectionSize { collection_size })
+    }
+
+    pub fn set_mint_protection(&self, protection: MintProtectionResponse) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetMintProtection {
+            reject_contracts: protection.reject_contracts,
+            max_mints_per_block: protection.max_mints_per_block,
+            min_blocks_between_mints: protection.min_blocks_between_mints,
+        })
+    }
+
+    pub fn set_compact_mint(&self, enabled: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetCompactMint { enabled })
+    }
+
+    pub fn set_reserved_supply(&self, reserved_supply: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetReservedSupply { reserved_supply })
+    }
+
+    pub fn set_transferable(
+        &self,
+        token_id: Option<String>,
+        transferable: bool,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetTransferable {
+            token_id,
+            transferable,
+        })
+    }
+
+    pub fn set_operator_filter(&self, mode: OperatorFilterMode) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::SetOperatorFilter { mode })
+    }
+
+    pub fn add_to_operator_registry(&self, addresses: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::AddToOperatorRegistry { addresses })
+    }
+
+    pub fn remove_from_operator_registry(&self, addresses: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveFromOperatorRegistry { addresses })
+    }
+
+    pub fn add_hook(
+        &self,
+        contract: impl Into<String>,
+        error_policy: Option<HookErrorPolicy>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::AddHook {
+            contract: contract.into(),
+            error_policy,
+        })
+    }
+
+    pub fn remove_hook(&self, contract: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::<Empty, E>::RemoveHook {
+            contract: contract.into(),
+        })
+    }
+
     /*** queries ***/
 
     pub fn owner_of<T: Into<String>>(
@@ -107,6 +485,9 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
             include_expired: Some(include_expired),
             start_after,
             limit,
+            start_at: None,
+            end_before: None,
+            order: None,
         };
         let res: OperatorsResponse = self.query(querier, req)?;
         Ok(res.operators)
@@ -162,6 +543,9 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
             owner: owner.into(),
             start_after,
             limit,
+            start_at: None,
+            end_before: None,
+            order: None,
         };
         self.query(querier, req)
     }
@@ -173,10 +557,330 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let req = QueryMsg::AllTokens { start_after, limit };
+        let req = QueryMsg::AllTokens {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        self.query(querier, req)
+    }
+
+    /// With PlayerX extension
+    pub fn sale_config(&self, querier: &QuerierWrapper) -> StdResult<Option<SaleConfigResponse>> {
+        self.query(querier, QueryMsg::SaleConfig {})
+    }
+
+    /// With PlayerX extension
+    pub fn holder_claim_config(
+        &self,
+        querier: &QuerierWrapper,
+    ) -> StdResult<Option<HolderClaimConfigResponse>> {
+        self.query(querier, QueryMsg::HolderClaimConfig {})
+    }
+
+    /// With PlayerX extension
+    pub fn source_token_claim<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<Option<String>> {
+        let req = QueryMsg::SourceTokenClaim {
+            token_id: token_id.into(),
+        };
+        let res: SourceTokenClaimResponse = self.query(querier, req)?;
+        Ok(res.claimed_by)
+    }
+
+    /// With PlayerX extension
+    pub fn mint_passes(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<MintPass>> {
+        let req = QueryMsg::MintPasses {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: MintPassesResponse = self.query(querier, req)?;
+        Ok(res.mint_passes)
+    }
+
+    /// With PlayerX extension
+    pub fn raffle(&self, querier: &QuerierWrapper) -> StdResult<Option<RaffleResponse>> {
+        self.query(querier, QueryMsg::Raffle {})
+    }
+
+    /// With PlayerX extension
+    pub fn raffle_entry<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<Option<RaffleStatus>> {
+        let req = QueryMsg::RaffleEntry {
+            address: address.into(),
+        };
+        let res: RaffleEntryResponse = self.query(querier, req)?;
+        Ok(res.status)
+    }
+
+    /// With PlayerX extension
+    pub fn raffle_draw(&self, querier: &QuerierWrapper) -> StdResult<Option<RaffleDrawResponse>> {
+        self.query(querier, QueryMsg::RaffleDraw {})
+    }
+
+    /// With PlayerX extension
+    pub fn raffle_winners(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<RaffleWinner>> {
+        let req = QueryMsg::RaffleWinners {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: RaffleWinnersResponse = self.query(querier, req)?;
+        Ok(res.winners)
+    }
+
+    /// With PlayerX extension
+    pub fn raffle_refund<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<RaffleRefundResponse> {
+        let req = QueryMsg::RaffleRefund {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    /// With PlayerX extension
+    pub fn collection_size(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        let res: CollectionSizeResponse = self.query(querier, QueryMsg::CollectionSize {})?;
+        Ok(res.collection_size)
+    }
+
+    /// With PlayerX extension
+    pub fn mint_protection(&self, querier: &QuerierWrapper) -> StdResult<MintProtectionResponse> {
+        self.query(querier, QueryMsg::MintProtection {})
+    }
+
+    /// With PlayerX extension
+    pub fn compact_mint(&self, querier: &QuerierWrapper) -> StdResult<bool> {
+        let res: CompactMintResponse = self.query(querier, QueryMsg::CompactMint {})?;
+        Ok(res.enabled)
+    }
+
+    /// With PlayerX extension
+    pub fn reserved_supply(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        let res: ReservedSupplyResponse = self.query(querier, QueryMsg::ReservedSupply {})?;
+        Ok(res.reserved_supply)
+    }
+
+    /// With PlayerX extension
+    pub fn base_token_uri(&self, querier: &QuerierWrapper) -> StdResult<String> {
+        let res: BaseTokenUriResponse = self.query(querier, QueryMsg::BaseTokenUri {})?;
+        Ok(res.base_token_uri)
+    }
+
+    /// With PlayerX extension
+    pub fn withdraw_address(&self, querier: &QuerierWrapper) -> StdResult<Option<String>> {
+        self.query(querier, QueryMsg::WithdrawAddress {})
+    }
+
+    /// With PlayerX extension
+    pub fn operator_filter(&self, querier: &QuerierWrapper) -> StdResult<OperatorFilterMode> {
+        let res: OperatorFilterResponse = self.query(querier, QueryMsg::OperatorFilter {})?;
+        Ok(res.mode)
+    }
+
+    /// With PlayerX extension
+    pub fn operator_registry(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<String>> {
+        let req = QueryMsg::OperatorRegistry {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: OperatorRegistryResponse = self.query(querier, req)?;
+        Ok(res.operators)
+    }
+
+    /// With PlayerX extension
+    pub fn tokens_with_info<T: Into<String>, U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<U>> {
+        let req = QueryMsg::TokensWithInfo {
+            owner: owner.into(),
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        self.query(querier, req)
+    }
+
+    /// With PlayerX extension
+    pub fn all_tokens_with_info<U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<U>> {
+        let req = QueryMsg::AllTokensWithInfo {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
         self.query(querier, req)
     }
 
+    /// With PlayerX extension
+    pub fn balance<T: Into<String>>(&self, querier: &QuerierWrapper, owner: T) -> StdResult<u64> {
+        let req = QueryMsg::Balance {
+            owner: owner.into(),
+        };
+        let res: BalanceResponse = self.query(querier, req)?;
+        Ok(res.balance)
+    }
+
+    /// With PlayerX extension
+    pub fn holders(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Holder>> {
+        let req = QueryMsg::Holders {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: HoldersResponse = self.query(querier, req)?;
+        Ok(res.holders)
+    }
+
+    /// With PlayerX extension
+    pub fn num_holders(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        let res: NumHoldersResponse = self.query(querier, QueryMsg::NumHolders {})?;
+        Ok(res.count)
+    }
+
+    /// With PlayerX extension
+    pub fn hooks(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<HookResponse>> {
+        let req = QueryMsg::Hooks {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: HooksResponse = self.query(querier, req)?;
+        Ok(res.hooks)
+    }
+
+    /// With PlayerX extension
+    pub fn allocation<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        address: T,
+    ) -> StdResult<AllocationResponse> {
+        let req = QueryMsg::Allocation {
+            address: address.into(),
+        };
+        self.query(querier, req)
+    }
+
+    /// With PlayerX extension
+    pub fn og_list(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Allocation>> {
+        let req = QueryMsg::OgList {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: AllocationsResponse = self.query(querier, req)?;
+        Ok(res.allocations)
+    }
+
+    /// With PlayerX extension
+    pub fn allowlist(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Allocation>> {
+        let req = QueryMsg::Allowlist {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: AllocationsResponse = self.query(querier, req)?;
+        Ok(res.allocations)
+    }
+
+    /// With PlayerX extension
+    pub fn timelock_delay(&self, querier: &QuerierWrapper) -> StdResult<u64> {
+        let res: TimelockDelayResponse = self.query(querier, QueryMsg::TimelockDelay {})?;
+        Ok(res.seconds)
+    }
+
+    /// With PlayerX extension
+    pub fn pending_changes(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PendingChange>> {
+        let req = QueryMsg::PendingChanges {
+            start_after,
+            limit,
+            start_at: None,
+            end_before: None,
+            order: None,
+        };
+        let res: PendingChangesResponse = self.query(querier, req)?;
+        Ok(res.changes)
+    }
+
     /// returns true if the contract supports the metadata extension
     pub fn has_metadata(&self, querier: &QuerierWrapper) -> bool {
         self.contract_info(querier).is_ok()
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, StdResult, SubMsg, WasmMsg};
+
+use crate::msg::HookErrorPolicy;
+
+/// Reply id of hook notifications sent with `HookErrorPolicy::Ignore`
+pub const HOOK_REPLY_ID: u64 = 1;
+
+/// Cw721HookMsg should be de/serialized under `Cw721Hook()` variant in a ExecuteMsg
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// A token moved from `previous_owner` to `owner`
+    Transfer {
+        token_id: String,
+        previous_owner: String,
+        owner: String,
+    },
+    /// Tokens were minted to `owner`
+    Mint {
+        token_ids: Vec<String>,
+        owner: String,
+    },
+}
+
+impl Cw721HookMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = HookExecuteMsg::Cw721Hook(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a sub_msg sending this message to the named contract, failures
+    /// revert the transaction unless the error policy ignores them
+    pub fn into_sub_msg<T: Into<String>, C>(
+        self,
+        contract_addr: T,
+        error_policy: &HookErrorPolicy,
+    ) -> StdResult<SubMsg<C>>
+    where
+        C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(match error_policy {
+            HookErrorPolicy::Abort => SubMsg::new(execute),
+            HookErrorPolicy::Ignore => SubMsg::reply_on_error(execute, HOOK_REPLY_ID),
+        })
+    }
+}
+
+/// This is just a helper to properly serialize the above message.
+/// The actual hook contract should include this variant in the larger ExecuteMsg enum
+#[cw_serde]
+enum HookExecuteMsg {
+    Cw721Hook(Cw721HookMsg),
+}
@@ -1,16 +1,27 @@
 pub mod error;
 mod execute;
+pub mod extension;
 pub mod helpers;
+pub mod hook;
 pub mod msg;
+#[cfg(any(test, feature = "multitest"))]
+pub mod multitest;
+pub mod pagination;
 mod query;
+pub mod raffle;
+pub mod receiver;
 pub mod state;
 pub mod upgrades;
+pub mod validation;
 
 #[cfg(test)]
 mod contract_tests;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+pub use crate::extension::Cw721Extension;
+pub use crate::hook::Cw721HookMsg;
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+pub use crate::receiver::Cw721BatchReceiveMsg;
 pub use crate::state::Cw721Contract;
 
 // These types are re-exported so that contracts interacting with this
@@ -26,22 +37,19 @@ use cosmwasm_std::Empty;
 // This is a simple type to let us handle empty extensions
 pub type Extension = Option<Empty>;
 
+// Denom mint prices are paid in
+pub const MINT_DENOM: &str = "usei";
+
 // Version info for migration
 pub const CONTRACT_NAME: &str = "crates.io:cw721-base";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// currently we only support migrating from 0.16.0. this is ok for now because
-// we have not released any 0.16.x where x != 0
-//
-// TODO: parse semvar so that any version 0.16.x can be migrated from
-pub const EXPECTED_FROM_VERSION: &str = "0.16.0";
-
 pub mod entry {
     use super::*;
 
     #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
     // This makes a conscious choice on the various generics used by the contract
     #[cfg_attr(not(feature = "library"), entry_point)]
@@ -75,16 +83,29 @@ pub mod entry {
     }
 
     #[cfg_attr(not(feature = "library"), entry_point)]
-    pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-        // make sure the correct contract is being upgraded, and it's being
-        // upgraded from the correct version.
-        cw2::assert_contract_version(deps.as_ref().storage, CONTRACT_NAME, EXPECTED_FROM_VERSION)?;
-
-        // update contract version
-        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty, Empty, Empty>::default();
+        tract.reply(deps, env, msg)
+    }
 
-        // perform the upgrade
-        upgrades::v0_17::migrate::<Extension, Empty, Empty, Empty>(deps)
+    #[cfg_attr(not(feature = "library"), entry_point)]
+    pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        match msg.from_upstream {
+            // convert an upstream collection into this contract's storage layout
+            Some(upstream) => upgrades::upstream_v0_18::migrate::<Extension, Empty, Empty, Empty>(
+                deps,
+                CONTRACT_NAME,
+                CONTRACT_VERSION,
+                upstream,
+            ),
+            // make sure the correct contract is being upgraded and run every
+            // upgrade step since the stored version
+            None => upgrades::migrate::<Extension, Empty, Empty, Empty>(
+                deps,
+                CONTRACT_NAME,
+                CONTRACT_VERSION,
+            ),
+        }
     }
 }
 
@@ -110,7 +131,7 @@ mod tests {
             InstantiateMsg {
                 name: "".into(),
                 symbol: "".into(),
-                base_token_uri: "".into(),
+                base_token_uri: "ipfs://playerx".into(),
                 collection_size: 10,
                 max_per_public: 5,
                 max_per_allowlist: 1,
@@ -149,7 +170,7 @@ mod tests {
             InstantiateMsg {
                 name: "".into(),
                 symbol: "".into(),
-                base_token_uri: "".into(),
+                base_token_uri: "ipfs://playerx".into(),
                 collection_size: 10,
                 max_per_public: 5,
                 max_per_allowlist: 1,
@@ -1,9 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Coin, Uint128};
-use cw721::Expiration;
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+use cw721::{Cw721ReceiveMsg, Expiration};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 use schemars::JsonSchema;
 
+use crate::pagination::OrderBy;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Name of the NFT contract
@@ -23,6 +25,32 @@ pub struct InstantiateMsg {
     pub max_per_public: u64,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Converts a collection deployed from upstream cw721-base 0.18, leave unset
+    /// when upgrading a collection deployed from this contract
+    pub from_upstream: Option<UpstreamMigrateMsg>,
+}
+
+/// Settings upstream cw721-base collections don't store
+#[cw_serde]
+pub struct UpstreamMigrateMsg {
+    // Base token URI, token URIs stored per token have to match it
+    pub base_token_uri: String,
+    // Collection size, can't be below the number of existing tokens
+    pub collection_size: u64,
+    // Sale config, all sales start closed
+    pub og_price: Uint128,
+    pub allowlist_price: Uint128,
+    pub public_price: Uint128,
+    pub max_per_og: u64,
+    pub max_per_allowlist: u64,
+    pub max_per_public: u64,
+    // Withdraw address, defaults to the stored one, else the contract owner
+    pub withdraw_address: Option<String>,
+}
+
 /// This is like Cw721ExecuteMsg but we add a few mint configs and functions
 #[cw_ownable_execute]
 #[cw_serde]
@@ -36,6 +64,19 @@ pub enum ExecuteMsg<T, E> {
         token_id: String,
         msg: Binary,
     },
+    /// Transfers several tokens to another account, all or nothing
+    BatchTransferNft {
+        recipient: String,
+        token_ids: Vec<String>,
+    },
+    /// Sends several tokens to a contract, all or nothing. The contract receives
+    /// one `ReceiveNft` per token, or a single `BatchReceiveNft` if aggregate is set.
+    BatchSendNft {
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+        aggregate: Option<bool>,
+    },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
@@ -45,6 +86,17 @@ pub enum ExecuteMsg<T, E> {
     },
     /// Remove previously granted Approval
     Revoke { spender: String, token_id: String },
+    /// Approve spender for several tokens, all or nothing
+    BatchApprove {
+        spender: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    },
+    /// Remove previously granted Approval from several tokens, all or nothing
+    BatchRevoke {
+        spender: String,
+        token_ids: Vec<String>,
+    },
     /// Allows operator to transfer / send any token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     ApproveAll {
@@ -54,8 +106,13 @@ pub enum ExecuteMsg<T, E> {
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
 
-    /// Mint for the team
-    MintTeam { quantity: u64, extension: T },
+    /// Mint for the team. `transferable` sets the transferable flag of the
+    /// minted tokens, otherwise they follow the collection flag.
+    MintTeam {
+        quantity: u64,
+        extension: T,
+        transferable: Option<bool>,
+    },
 
     /// Mint for og
     MintOg { quantity: u64, extension: T },
@@ -67,6 +124,14 @@ pub enum ExecuteMsg<T, E> {
     /// Mint for public
     MintPublic { quantity: u64, extension: T },
 
+    /// Free mint for holders of the source collection. Each group of
+    /// `source_tokens_per_claim` source tokens, owned by the sender and not
+    /// claimed with before, mints `mints_per_claim` tokens.
+    HolderClaim {
+        source_token_ids: Vec<String>,
+        extension: T,
+    },
+
     /// DO NOT USE: This mint is disabled
     Mint {
         /// Unique ID of the NFT
@@ -87,10 +152,20 @@ pub enum ExecuteMsg<T, E> {
     /// Extension msg
     Extension { msg: E },
 
-    /// Sets address to send withdrawn fees to. Only owner can call this.
+    /// Queues setting the address to send withdrawn fees to, applied with
+    /// `ApplyChange` after the timelock delay. Only owner can call this.
     SetWithdrawAddress { address: String },
-    /// Removes the withdraw address, so fees are sent to the contract. Only owner can call this.
+    /// Queues removing the withdraw address, so fees are sent to the contract.
+    /// Applied with `ApplyChange` after the timelock delay. Only owner can call this.
     RemoveWithdrawAddress {},
+    /// Sets the delay of timelocked changes. Longer delays apply immediately,
+    /// shorter ones are queued like any other change. Only owner can call this.
+    SetTimelockDelay { seconds: u64 },
+    /// Cancels a pending change. Only owner can call this.
+    CancelChange { id: u64 },
+    /// Applies a pending change once its effective time has passed. Anyone can
+    /// call this, since the change was queued by the owner.
+    ApplyChange { id: u64 },
     /// Withdraw from the contract to the given address. Anyone can call this,
     /// which is okay since withdraw address has been set by owner.
     WithdrawFunds { amount: Coin },
@@ -105,12 +180,31 @@ pub enum ExecuteMsg<T, E> {
         max_per_allowlist: u64,
         max_per_public: u64,
     },
-    /// Add addresses to allowlist
-    AddToAllowlist { addresses: Vec<String> },
+    /// Updates only the provided sale config fields
+    UpdateSaleConfig {
+        og_price: Option<Uint128>,
+        allowlist_price: Option<Uint128>,
+        public_price: Option<Uint128>,
+        max_per_og: Option<u64>,
+        max_per_allowlist: Option<u64>,
+        max_per_public: Option<u64>,
+    },
+    /// Sets the collection whose holders can claim free mints and the claim ratio.
+    /// `mints_per_claim` can be at most the collection size.
+    SetHolderClaimConfig {
+        source_collection: String,
+        source_tokens_per_claim: u64,
+        mints_per_claim: u64,
+        open: bool,
+    },
+    /// Sets how many tokens each address can mint from the allowlist,
+    /// replacing what was left of a previous allocation
+    AddToAllowlist { entries: Vec<(String, u64)> },
     /// Remove addresses from allowlist
     RemoveFromAllowlist { addresses: Vec<String> },
-    /// Add addresses to og list
-    AddToOgList { addresses: Vec<String> },
+    /// Sets how many tokens each address can mint from the og list,
+    /// replacing what was left of a previous allocation
+    AddToOgList { entries: Vec<(String, u64)> },
     /// Remove addresses from allowlist
     RemoveFromOgList { addresses: Vec<String> },
     /// Sets state of allowlist sale
@@ -119,8 +213,88 @@ pub enum ExecuteMsg<T, E> {
     SetOgSale { open: bool },
     /// Sets state of public sale
     SetPublicSale { open: bool },
+    /// Sets the protections of public mints against bots. Only owner can call this.
+    SetMintProtection {
+        /// rejects minters that are contracts
+        reject_contracts: bool,
+        /// caps the tokens minted publicly in a single block
+        max_mints_per_block: Option<u64>,
+        /// blocks an address has to wait between its public mints
+        min_blocks_between_mints: Option<u64>,
+    },
+    /// Sets whether consecutive tokens minted together are stored as a single
+    /// batch record, split up as they are transferred or approved. Queries
+    /// return the same either way. Only owner can call this.
+    SetCompactMint { enabled: bool },
     /// Sets collection size
     SetCollectionSize { collection_size: u64 },
+    /// Reserves supply for team mints, which og, allowlist and public mints can't
+    /// consume. Team mints draw the reservation down. Only owner can call this.
+    SetReservedSupply { reserved_supply: u64 },
+    /// Sets whether tokens can be transferred, sent or approved. Applies to the given
+    /// token if set, otherwise to the whole collection. Only owner can call this.
+    SetTransferable {
+        token_id: Option<String>,
+        transferable: bool,
+    },
+    /// Sets how the operator registry is applied to approvals and third-party
+    /// transfers. Only owner can call this.
+    SetOperatorFilter { mode: OperatorFilterMode },
+    /// Add addresses to operator registry
+    AddToOperatorRegistry { addresses: Vec<String> },
+    /// Remove addresses from operator registry
+    RemoveFromOperatorRegistry { addresses: Vec<String> },
+    /// Notify a contract of every transfer and mint. Only owner can call this.
+    AddHook {
+        contract: String,
+        /// unset reverts transfers and mints when the hook fails
+        error_policy: Option<HookErrorPolicy>,
+    },
+    /// Stop notifying a contract. Only owner can call this.
+    RemoveHook { contract: String },
+
+    /// Redeems a pass sent with `SendNft` by a registered mint pass collection,
+    /// minting tokens to the sender of the pass. `msg` is the json encoded
+    /// extension of the minted tokens, the default extension if empty.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Registers a mint pass collection, or updates how its passes are redeemed.
+    /// Only owner can call this.
+    SetMintPass {
+        collection: String,
+        mints_per_pass: u64,
+        /// burns redeemed passes, otherwise they're locked in this contract.
+        /// Passes of collections that can't burn are locked instead.
+        burn: bool,
+    },
+    /// Stop redeeming passes of a collection. Only owner can call this.
+    RemoveMintPass { collection: String },
+
+    /// Opens raffle registration until `draw_height`. `entropy_commitment` is
+    /// the hex encoded sha256 of the entropy revealed to draw. Entrants can be
+    /// refunded from `refund_deadline` if the raffle still wasn't drawn by then.
+    /// Only owner can call this, and only until the first entry. A collection
+    /// runs a single raffle: once it has entries it can't be replaced, even
+    /// after it was drawn or refunded.
+    SetRaffle {
+        price: Uint128,
+        winners: u64,
+        entropy_commitment: String,
+        draw_height: u64,
+        refund_deadline: u64,
+    },
+    /// Registers the sender in the raffle, escrowing the raffle price and
+    /// refunding anything sent above it
+    EnterRaffle {},
+    /// Draws the winners once the draw height is reached and before the refund
+    /// deadline, seeded by the committed entropy, the entries and the draw
+    /// height. Only owner can call this.
+    DrawRaffle { entropy: String },
+    /// Mints the token won in the raffle
+    ClaimRaffleMint { extension: T },
+    /// Refunds the escrowed price of an entry that didn't win, of a winning
+    /// entry once the collection is minted out, or of any entry once the refund
+    /// deadline passed without a draw
+    ClaimRaffleRefund {},
 }
 
 #[cw_ownable_query]
@@ -162,6 +336,12 @@ pub enum QueryMsg<Q: JsonSchema> {
         include_expired: Option<bool>,
         start_after: Option<String>,
         limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
     },
     /// Total number of tokens issued
     #[returns(cw721::NumTokensResponse)]
@@ -174,12 +354,12 @@ pub enum QueryMsg<Q: JsonSchema> {
     /// With MetaData Extension.
     /// Returns metadata about one particular token, based on *ERC721 Metadata JSON Schema*
     /// but directly from the contract
-    #[returns(cw721::NftInfoResponse<Q>)]
+    #[returns(cw721::NftInfoResponse<NftInfoExtension<Q>>)]
     NftInfo { token_id: String },
     /// With MetaData Extension.
     /// Returns the result of both `NftInfo` and `OwnerOf` as one query as an optimization
     /// for clients
-    #[returns(cw721::AllNftInfoResponse<Q>)]
+    #[returns(cw721::AllNftInfoResponse<NftInfoExtension<Q>>)]
     AllNftInfo {
         token_id: String,
         /// unset or false will filter out expired approvals, you must set to true to see them
@@ -193,6 +373,12 @@ pub enum QueryMsg<Q: JsonSchema> {
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
     },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
@@ -200,6 +386,40 @@ pub enum QueryMsg<Q: JsonSchema> {
     AllTokens {
         start_after: Option<String>,
         limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// With Enumerable extension.
+    /// Returns all tokens owned by the given address with their owner, uri and extension
+    #[returns(TokensWithInfoResponse<NftInfoExtension<Q>>)]
+    TokensWithInfo {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+    /// With Enumerable extension.
+    /// Requires pagination. Lists all tokens with their owner, uri and extension
+    #[returns(TokensWithInfoResponse<NftInfoExtension<Q>>)]
+    AllTokensWithInfo {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
     },
 
     /// Return the admin
@@ -213,10 +433,22 @@ pub enum QueryMsg<Q: JsonSchema> {
     #[returns(Option<String>)]
     WithdrawAddress {},
 
+    /// Returns the protections of public mints
+    #[returns(MintProtectionResponse)]
+    MintProtection {},
+
+    /// Returns whether tokens are minted in compact batches
+    #[returns(CompactMintResponse)]
+    CompactMint {},
+
     /// Returns collection size
     #[returns(CollectionSizeResponse)]
     CollectionSize {},
 
+    /// Returns what's left of the supply reserved for team mints
+    #[returns(ReservedSupplyResponse)]
+    ReservedSupply {},
+
     /// Returns base token uri
     #[returns(BaseTokenUriResponse)]
     BaseTokenUri {},
@@ -224,6 +456,147 @@ pub enum QueryMsg<Q: JsonSchema> {
     /// Returns sale config
     #[returns(SaleConfigResponse)]
     SaleConfig {},
+
+    /// Returns the holder claim config, if one was set
+    #[returns(Option<HolderClaimConfigResponse>)]
+    HolderClaimConfig {},
+
+    /// Returns who claimed with a token of the current source collection
+    #[returns(SourceTokenClaimResponse)]
+    SourceTokenClaim { token_id: String },
+
+    /// Returns operator filter mode
+    #[returns(OperatorFilterResponse)]
+    OperatorFilter {},
+
+    /// Lists addresses in the operator registry
+    #[returns(OperatorRegistryResponse)]
+    OperatorRegistry {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// Number of tokens held by an address
+    #[returns(BalanceResponse)]
+    Balance { owner: String },
+
+    /// Lists addresses holding at least one token, with their balances
+    #[returns(HoldersResponse)]
+    Holders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// Number of addresses holding at least one token
+    #[returns(NumHoldersResponse)]
+    NumHolders {},
+
+    /// Lists contracts notified of transfers and mints
+    #[returns(HooksResponse)]
+    Hooks {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// Returns the raffle config and number of entries, if a raffle was set
+    #[returns(Option<RaffleResponse>)]
+    Raffle {},
+    /// Returns the raffle status of an address
+    #[returns(RaffleEntryResponse)]
+    RaffleEntry { address: String },
+    /// Returns how the raffle was drawn, once it was
+    #[returns(Option<RaffleDrawResponse>)]
+    RaffleDraw {},
+    /// Lists raffle winners and whether they claimed their token
+    #[returns(RaffleWinnersResponse)]
+    RaffleWinners {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+    /// Returns what an address can have refunded from the raffle
+    #[returns(RaffleRefundResponse)]
+    RaffleRefund { address: String },
+
+    /// Lists mint pass collections and how their passes are redeemed
+    #[returns(MintPassesResponse)]
+    MintPasses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// Returns what's left of the og and allowlist allocations of an address
+    #[returns(AllocationResponse)]
+    Allocation { address: String },
+    /// Lists og list entries with what's left of their allocation
+    #[returns(AllocationsResponse)]
+    OgList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+    /// Lists allowlist entries with what's left of their allocation
+    #[returns(AllocationsResponse)]
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
+
+    /// Returns how long changes are queued before they can be applied
+    #[returns(TimelockDelayResponse)]
+    TimelockDelay {},
+    /// Lists queued changes with the time they can be applied at
+    #[returns(PendingChangesResponse)]
+    PendingChanges {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// inclusive alternative to start_after, takes precedence if both are set
+        start_at: Option<String>,
+        /// exclusive bound where the page stops
+        end_before: Option<String>,
+        /// unset iterates in ascending order
+        order: Option<OrderBy>,
+    },
 }
 
 /// Shows who can mint these tokens
@@ -237,11 +610,263 @@ pub struct CollectionSizeResponse {
     pub collection_size: u64,
 }
 
+#[cw_serde]
+pub struct CompactMintResponse {
+    pub enabled: bool,
+}
+
+#[cw_serde]
+pub struct ReservedSupplyResponse {
+    pub reserved_supply: u64,
+}
+
 #[cw_serde]
 pub struct BaseTokenUriResponse {
     pub base_token_uri: String,
 }
 
+/// Controls which addresses can become spenders or operators, and transfer
+/// tokens on behalf of their owner
+#[cw_serde]
+#[derive(Default)]
+pub enum OperatorFilterMode {
+    /// Any address can be approved
+    #[default]
+    Disabled,
+    /// Only addresses in the operator registry can be approved
+    Allowlist,
+    /// Addresses in the operator registry can't be approved
+    Blocklist,
+}
+
+/// What happens to a transfer or mint when a hook contract fails
+#[cw_serde]
+#[derive(Default)]
+pub enum HookErrorPolicy {
+    /// The hook error reverts the transfer or mint
+    #[default]
+    Abort,
+    /// The hook error is ignored
+    Ignore,
+}
+
+#[cw_serde]
+pub struct HookResponse {
+    pub contract: String,
+    pub error_policy: HookErrorPolicy,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<HookResponse>,
+}
+
+#[cw_serde]
+pub struct RaffleConfig {
+    pub price: Uint128,
+    pub winners: u64,
+    pub entropy_commitment: String,
+    /// Height entries close at and the draw is possible from
+    pub draw_height: u64,
+    /// Height the draw is possible until, entries are refundable from then
+    /// if the raffle wasn't drawn
+    pub refund_deadline: u64,
+}
+
+#[cw_serde]
+pub struct RaffleResponse {
+    pub config: RaffleConfig,
+    pub num_entries: u64,
+    pub drawn: bool,
+}
+
+#[cw_serde]
+pub enum RaffleStatus {
+    /// Waiting for the draw
+    Entered,
+    Won,
+    /// Drawn without winning
+    Lost,
+    /// Won and minted
+    Claimed,
+    Refunded,
+}
+
+#[cw_serde]
+pub struct RaffleEntryResponse {
+    /// Unset for addresses that didn't enter
+    pub status: Option<RaffleStatus>,
+}
+
+#[cw_serde]
+pub struct RaffleDrawResponse {
+    /// Hex encoded seed the winners were drawn with
+    pub seed: String,
+    pub entropy: String,
+    /// Hex encoded hash chain of the entrants, mixed into the seed
+    pub entries_hash: String,
+    pub num_entries: u64,
+    pub num_winners: u64,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct RaffleWinner {
+    pub address: String,
+    pub claimed: bool,
+}
+
+#[cw_serde]
+pub struct RaffleWinnersResponse {
+    pub winners: Vec<RaffleWinner>,
+}
+
+#[cw_serde]
+pub struct RaffleRefundResponse {
+    /// Amount that can be refunded now
+    pub refundable: Uint128,
+    pub refunded: bool,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MintProtectionResponse {
+    pub reject_contracts: bool,
+    pub max_mints_per_block: Option<u64>,
+    pub min_blocks_between_mints: Option<u64>,
+}
+
+#[cw_serde]
+pub struct MintPassConfig {
+    pub mints_per_pass: u64,
+    pub burn: bool,
+}
+
+#[cw_serde]
+pub struct MintPass {
+    pub collection: String,
+    pub mints_per_pass: u64,
+    pub burn: bool,
+}
+
+#[cw_serde]
+pub struct MintPassesResponse {
+    pub mint_passes: Vec<MintPass>,
+}
+
+#[cw_serde]
+pub struct HolderClaimConfigResponse {
+    pub source_collection: Addr,
+    pub source_tokens_per_claim: u64,
+    pub mints_per_claim: u64,
+    pub open: bool,
+}
+
+#[cw_serde]
+pub struct SourceTokenClaimResponse {
+    /// Unset if the token hasn't been claimed with
+    pub claimed_by: Option<String>,
+}
+
+#[cw_serde]
+pub struct AllocationResponse {
+    pub og: u64,
+    pub allowlist: u64,
+}
+
+#[cw_serde]
+pub struct Allocation {
+    pub address: String,
+    pub remaining: u64,
+}
+
+#[cw_serde]
+pub struct AllocationsResponse {
+    pub allocations: Vec<Allocation>,
+}
+
+/// Owner settings that only change once the timelock delay has passed
+#[cw_serde]
+pub enum TimelockedChange {
+    /// Sets the withdraw address, or removes it if unset
+    WithdrawAddress { address: Option<String> },
+    /// Shortens the timelock delay
+    TimelockDelay { seconds: u64 },
+    /// Serialized extension message, applied by the contract extension
+    Extension { msg: Binary },
+}
+
+#[cw_serde]
+pub struct PendingChange {
+    pub id: u64,
+    pub change: TimelockedChange,
+    /// Block time the change can be applied from
+    pub effective_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct PendingChangesResponse {
+    pub changes: Vec<PendingChange>,
+}
+
+#[cw_serde]
+pub struct TimelockDelayResponse {
+    pub seconds: u64,
+}
+
+#[cw_serde]
+pub struct OperatorFilterResponse {
+    pub mode: OperatorFilterMode,
+}
+
+#[cw_serde]
+pub struct OperatorRegistryResponse {
+    pub operators: Vec<String>,
+}
+
+/// Token extension returned by `NftInfo` and `AllNftInfo`, adding the
+/// contract-managed token flags to the stored extension
+#[cw_serde]
+pub struct NftInfoExtension<T> {
+    pub transferable: bool,
+    pub extension: T,
+}
+
+#[cw_serde]
+pub struct TokenWithInfo<T> {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub extension: T,
+}
+
+#[cw_serde]
+pub struct TokensWithInfoResponse<T> {
+    pub tokens: Vec<TokenWithInfo<T>>,
+}
+
+#[cw_serde]
+pub struct BalanceResponse {
+    pub balance: u64,
+}
+
+#[cw_serde]
+pub struct Holder {
+    pub address: String,
+    pub balance: u64,
+}
+
+#[cw_serde]
+pub struct HoldersResponse {
+    pub holders: Vec<Holder>,
+}
+
+#[cw_serde]
+pub struct NumHoldersResponse {
+    pub count: u64,
+}
+
 #[cw_serde]
 pub struct SaleConfigResponse {
     pub og_price: Uint128,
@@ -0,0 +1,579 @@
+//! Integration harness running collections in a cw-multi-test `App`, so bank
+//! transfers, receive callbacks and hook sub messages are actually executed.
+//! Enabled in this crate's tests and for other crates with the `multitest` feature.
+
+pub mod receiver;
+#[cfg(test)]
+mod tests;
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use cosmwasm_std::{coins, to_json_binary, Addr, Binary, Coin, Empty, Timestamp, Uint128};
+use cw721::{NumTokensResponse, OwnerOfResponse};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use self::receiver::{receiver_contract, ReceiverInstantiateMsg};
+use crate::helpers::mint_funds;
+use crate::msg::{
+    BalanceResponse, HookErrorPolicy, PendingChangesResponse, RaffleResponse, SaleConfigResponse,
+    TimelockDelayResponse,
+};
+use crate::raffle;
+use crate::{entry, ExecuteMsg, Extension, InstantiateMsg, QueryMsg, MINT_DENOM};
+
+pub const OWNER: &str = "owner";
+pub const USERS: [&str; 3] = ["alice", "bob", "carol"];
+/// Balance of every account at genesis, in `MINT_DENOM`
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+/// Seconds between blocks when advancing by blocks
+pub const BLOCK_TIME: u64 = 5;
+
+pub const PRICE: u128 = 100_000;
+pub const COLLECTION_SIZE: u64 = 100;
+
+pub fn cw721_base_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(entry::execute, entry::instantiate, entry::query)
+        .with_reply(entry::reply)
+        .with_migrate(entry::migrate);
+    Box::new(contract)
+}
+
+/// Upstream cw721-base, a collection that lets owners burn their tokens
+pub fn cw721_upstream_contract() -> Box<dyn Contract<Empty>> {
+    use cw721_base_018::entry;
+    let contract = ContractWrapper::new(entry::execute, entry::instantiate, entry::query);
+    Box::new(contract)
+}
+
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        name: "PlayerX".to_string(),
+        symbol: "PX".to_string(),
+        base_token_uri: "ipfs://playerx".to_string(),
+        collection_size: COLLECTION_SIZE,
+        max_per_og: 1,
+        max_per_allowlist: 2,
+        max_per_public: 5,
+        og_price: Uint128::new(PRICE),
+        allowlist_price: Uint128::new(PRICE),
+        public_price: Uint128::new(PRICE),
+    }
+}
+
+/// Sale phases, at most one sale is open at a time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Closed,
+    Og,
+    Allowlist,
+    Public,
+}
+
+/// An `App` with funded accounts and a deployed collection. `T` is the token
+/// extension the collection mints with.
+pub struct Suite<T = Extension> {
+    pub app: App,
+    pub owner: Addr,
+    pub users: Vec<Addr>,
+    pub code_id: u64,
+    pub collection: Addr,
+    extension: PhantomData<T>,
+}
+
+impl Suite<Extension> {
+    /// cw721-base deployed with `instantiate_msg`
+    pub fn new() -> Self {
+        Self::with_contract(cw721_base_contract(), &instantiate_msg())
+    }
+}
+
+impl Default for Suite<Extension> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Suite<T>
+where
+    T: Serialize + Default + Debug,
+{
+    /// Stores `contract` and instantiates it from the owner with `msg`
+    pub fn with_contract(contract: Box<dyn Contract<Empty>>, msg: &impl Serialize) -> Self {
+        let owner = Addr::unchecked(OWNER);
+        let users: Vec<Addr> = USERS.iter().map(|user| Addr::unchecked(*user)).collect();
+        let mut app = App::new(|router, _, storage| {
+            for account in std::iter::once(&owner).chain(&users) {
+                router
+                    .bank
+                    .init_balance(storage, account, coins(INITIAL_BALANCE, MINT_DENOM))
+                    .unwrap();
+            }
+        });
+        let code_id = app.store_code(contract);
+        let collection = app
+            .instantiate_contract(
+                code_id,
+                owner.clone(),
+                msg,
+                &[],
+                "collection",
+                Some(OWNER.into()),
+            )
+            .unwrap();
+        Self {
+            app,
+            owner,
+            users,
+            code_id,
+            collection,
+            extension: PhantomData,
+        }
+    }
+
+    pub fn user(&self, index: usize) -> Addr {
+        self.users[index].clone()
+    }
+
+    /*** execute ***/
+
+    pub fn execute<M: Serialize + Debug>(
+        &mut self,
+        sender: &Addr,
+        msg: &M,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(sender.clone(), self.collection.clone(), msg, funds)
+    }
+
+    fn execute_owner(&mut self, msg: ExecuteMsg<T, Empty>) -> AnyResult<AppResponse> {
+        let owner = self.owner.clone();
+        self.execute(&owner, &msg, &[])
+    }
+
+    /// Opens the sale of `phase` and closes the others
+    pub fn set_phase(&mut self, phase: Phase) -> AnyResult<()> {
+        self.execute_owner(ExecuteMsg::SetOgSale {
+            open: phase == Phase::Og,
+        })?;
+        self.execute_owner(ExecuteMsg::SetAllowlistSale {
+            open: phase == Phase::Allowlist,
+        })?;
+        self.execute_owner(ExecuteMsg::SetPublicSale {
+            open: phase == Phase::Public,
+        })?;
+        Ok(())
+    }
+
+    /// Lists each address with its allocation
+    pub fn add_to_og_list(&mut self, entries: &[(&Addr, u64)]) -> AnyResult<AppResponse> {
+        let entries = entries
+            .iter()
+            .map(|(addr, allocation)| (addr.to_string(), *allocation))
+            .collect();
+        self.execute_owner(ExecuteMsg::AddToOgList { entries })
+    }
+
+    /// Lists each address with its allocation
+    pub fn add_to_allowlist(&mut self, entries: &[(&Addr, u64)]) -> AnyResult<AppResponse> {
+        let entries = entries
+            .iter()
+            .map(|(addr, allocation)| (addr.to_string(), *allocation))
+            .collect();
+        self.execute_owner(ExecuteMsg::AddToAllowlist { entries })
+    }
+
+    /// Queues the withdraw address change, see `apply_pending_changes`
+    pub fn set_withdraw_address(&mut self, address: &Addr) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::SetWithdrawAddress {
+            address: address.to_string(),
+        })
+    }
+
+    /// Waits out the timelock delay and applies every pending change
+    pub fn apply_pending_changes(&mut self) -> AnyResult<()> {
+        let delay: TimelockDelayResponse = self.query(&QueryMsg::TimelockDelay {})?;
+        self.advance_seconds(delay.seconds);
+
+        let pending: PendingChangesResponse = self.query(&QueryMsg::PendingChanges {
+            start_after: None,
+            limit: None,
+            start_at: None,
+            end_before: None,
+            order: None,
+        })?;
+        let owner = self.owner.clone();
+        for change in pending.changes {
+            let msg = ExecuteMsg::<T, Empty>::ApplyChange { id: change.id };
+            self.execute(&owner, &msg, &[])?;
+        }
+        Ok(())
+    }
+
+    pub fn withdraw_funds(&mut self, amount: u128) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::WithdrawFunds {
+            amount: Coin::new(amount, MINT_DENOM),
+        })
+    }
+
+    pub fn set_mint_protection(
+        &mut self,
+        reject_contracts: bool,
+        max_mints_per_block: Option<u64>,
+        min_blocks_between_mints: Option<u64>,
+    ) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::SetMintProtection {
+            reject_contracts,
+            max_mints_per_block,
+            min_blocks_between_mints,
+        })
+    }
+
+    pub fn mint_team(&mut self, quantity: u64) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::MintTeam {
+            quantity,
+            extension: T::default(),
+            transferable: None,
+        })
+    }
+
+    /// Mints in `phase` paying its price, see `mint_with_funds` to pay
+    /// anything else. Minting while closed attempts a public mint.
+    pub fn mint(&mut self, sender: &Addr, phase: Phase, quantity: u64) -> AnyResult<AppResponse> {
+        let config = self.sale_config();
+        let price = match phase {
+            Phase::Og => config.og_price,
+            Phase::Allowlist => config.allowlist_price,
+            Phase::Public | Phase::Closed => config.public_price,
+        };
+        self.mint_with_funds(sender, phase, quantity, &mint_funds(price, quantity))
+    }
+
+    pub fn mint_with_funds(
+        &mut self,
+        sender: &Addr,
+        phase: Phase,
+        quantity: u64,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        let extension = T::default();
+        let msg = match phase {
+            Phase::Og => ExecuteMsg::<T, Empty>::MintOg {
+                quantity,
+                extension,
+            },
+            Phase::Allowlist => ExecuteMsg::MintAllowlist {
+                quantity,
+                extension,
+            },
+            Phase::Public | Phase::Closed => ExecuteMsg::MintPublic {
+                quantity,
+                extension,
+            },
+        };
+        self.execute(sender, &msg, funds)
+    }
+
+    pub fn transfer(
+        &mut self,
+        sender: &Addr,
+        recipient: &Addr,
+        token_id: &str,
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<T, Empty>::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        };
+        self.execute(sender, &msg, &[])
+    }
+
+    pub fn send(
+        &mut self,
+        sender: &Addr,
+        contract: &Addr,
+        token_id: &str,
+        msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<T, Empty>::SendNft {
+            contract: contract.to_string(),
+            token_id: token_id.to_string(),
+            msg,
+        };
+        self.execute(sender, &msg, &[])
+    }
+
+    pub fn add_hook(
+        &mut self,
+        contract: &Addr,
+        error_policy: HookErrorPolicy,
+    ) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::AddHook {
+            contract: contract.to_string(),
+            error_policy: Some(error_policy),
+        })
+    }
+
+    /// Deploys a contract accepting sends and hooks, or rejecting them if `fail` is set
+    pub fn instantiate_receiver(&mut self, fail: bool) -> Addr {
+        let code_id = self.app.store_code(receiver_contract());
+        self.app
+            .instantiate_contract(
+                code_id,
+                self.owner.clone(),
+                &ReceiverInstantiateMsg { fail },
+                &[],
+                "receiver",
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Deploys another cw721-base collection owned by the owner, with `tokens`
+    /// team minted tokens given to `holder`
+    pub fn instantiate_source_collection(&mut self, holder: &Addr, tokens: u64) -> Addr {
+        let code_id = self.app.store_code(cw721_base_contract());
+        let source = self
+            .app
+            .instantiate_contract(
+                code_id,
+                self.owner.clone(),
+                &instantiate_msg(),
+                &[],
+                "source",
+                None,
+            )
+            .unwrap();
+        let mint = ExecuteMsg::<Extension, Empty>::MintTeam {
+            quantity: tokens,
+            extension: None,
+            transferable: None,
+        };
+        self.app
+            .execute_contract(self.owner.clone(), source.clone(), &mint, &[])
+            .unwrap();
+        for token_id in 0..tokens {
+            let transfer = ExecuteMsg::<Extension, Empty>::TransferNft {
+                recipient: holder.to_string(),
+                token_id: token_id.to_string(),
+            };
+            self.app
+                .execute_contract(self.owner.clone(), source.clone(), &transfer, &[])
+                .unwrap();
+        }
+        source
+    }
+
+    /// Instantiates an upstream cw721-base collection with `tokens` minted to `holder`
+    pub fn instantiate_upstream_collection(&mut self, holder: &Addr, tokens: u64) -> Addr {
+        let code_id = self.app.store_code(cw721_upstream_contract());
+        let msg = cw721_base_018::InstantiateMsg {
+            name: "Upstream".to_string(),
+            symbol: "UP".to_string(),
+            minter: self.owner.to_string(),
+        };
+        let source = self
+            .app
+            .instantiate_contract(code_id, self.owner.clone(), &msg, &[], "upstream", None)
+            .unwrap();
+        for token_id in 0..tokens {
+            let mint = cw721_base_018::ExecuteMsg::<Extension, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: holder.to_string(),
+                token_uri: None,
+                extension: None,
+            };
+            self.app
+                .execute_contract(self.owner.clone(), source.clone(), &mint, &[])
+                .unwrap();
+        }
+        source
+    }
+
+    pub fn set_holder_claim_config(
+        &mut self,
+        source_collection: &Addr,
+        source_tokens_per_claim: u64,
+        mints_per_claim: u64,
+    ) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::SetHolderClaimConfig {
+            source_collection: source_collection.to_string(),
+            source_tokens_per_claim,
+            mints_per_claim,
+            open: true,
+        })
+    }
+
+    pub fn holder_claim(
+        &mut self,
+        sender: &Addr,
+        source_token_ids: &[&str],
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<T, Empty>::HolderClaim {
+            source_token_ids: source_token_ids.iter().map(|id| id.to_string()).collect(),
+            extension: T::default(),
+        };
+        self.execute(sender, &msg, &[])
+    }
+
+    pub fn set_mint_pass(
+        &mut self,
+        collection: &Addr,
+        mints_per_pass: u64,
+        burn: bool,
+    ) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::SetMintPass {
+            collection: collection.to_string(),
+            mints_per_pass,
+            burn,
+        })
+    }
+
+    /// Sends a pass of `pass_collection` to the collection to redeem it
+    pub fn redeem_mint_pass(
+        &mut self,
+        sender: &Addr,
+        pass_collection: &Addr,
+        token_id: &str,
+    ) -> AnyResult<AppResponse> {
+        let msg = to_json_binary(&T::default())?;
+        self.redeem_mint_pass_with(sender, pass_collection, token_id, msg)
+    }
+
+    /// Sends a pass of `pass_collection` to the collection with `msg` to redeem it
+    pub fn redeem_mint_pass_with(
+        &mut self,
+        sender: &Addr,
+        pass_collection: &Addr,
+        token_id: &str,
+        msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<Extension, Empty>::SendNft {
+            contract: self.collection.to_string(),
+            token_id: token_id.to_string(),
+            msg,
+        };
+        self.app
+            .execute_contract(sender.clone(), pass_collection.clone(), &msg, &[])
+    }
+
+    pub fn set_raffle(
+        &mut self,
+        price: u128,
+        winners: u64,
+        entropy: &str,
+        draw_height: u64,
+        refund_deadline: u64,
+    ) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::SetRaffle {
+            price: Uint128::new(price),
+            winners,
+            entropy_commitment: raffle::entropy_commitment(entropy),
+            draw_height,
+            refund_deadline,
+        })
+    }
+
+    /// Enters the raffle paying its price
+    pub fn enter_raffle(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
+        let raffle: Option<RaffleResponse> = self.query(&QueryMsg::Raffle {})?;
+        let price = raffle.map_or(Uint128::zero(), |raffle| raffle.config.price);
+        let msg = ExecuteMsg::<T, Empty>::EnterRaffle {};
+        self.execute(sender, &msg, &mint_funds(price, 1))
+    }
+
+    pub fn draw_raffle(&mut self, entropy: &str) -> AnyResult<AppResponse> {
+        self.execute_owner(ExecuteMsg::DrawRaffle {
+            entropy: entropy.to_string(),
+        })
+    }
+
+    pub fn claim_raffle_mint(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<T, Empty>::ClaimRaffleMint {
+            extension: T::default(),
+        };
+        self.execute(sender, &msg, &[])
+    }
+
+    pub fn claim_raffle_refund(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
+        let msg = ExecuteMsg::<T, Empty>::ClaimRaffleRefund {};
+        self.execute(sender, &msg, &[])
+    }
+
+    /*** time travel ***/
+
+    /// Moves the chain forward by `blocks` blocks of `BLOCK_TIME` seconds
+    pub fn advance_blocks(&mut self, blocks: u64) {
+        self.app.update_block(|block| {
+            block.height += blocks;
+            block.time = block.time.plus_seconds(blocks * BLOCK_TIME);
+        });
+    }
+
+    /// Moves the chain forward by `seconds`, in as many blocks as that covers
+    pub fn advance_seconds(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.height += (seconds / BLOCK_TIME).max(1);
+            block.time = block.time.plus_seconds(seconds);
+        });
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.app.block_info().height
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    /*** queries ***/
+
+    pub fn query<R: DeserializeOwned>(&self, msg: &QueryMsg<Empty>) -> AnyResult<R> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart(self.collection.clone(), msg)?)
+    }
+
+    pub fn sale_config(&self) -> SaleConfigResponse {
+        self.query(&QueryMsg::SaleConfig {}).unwrap()
+    }
+
+    pub fn owner_of(&self, token_id: &str) -> String {
+        let res: OwnerOfResponse = self
+            .query(&QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            })
+            .unwrap();
+        res.owner
+    }
+
+    pub fn num_tokens(&self) -> u64 {
+        let res: NumTokensResponse = self.query(&QueryMsg::NumTokens {}).unwrap();
+        res.count
+    }
+
+    /// Number of tokens held by `owner`
+    pub fn nft_balance(&self, owner: &Addr) -> u64 {
+        let res: BalanceResponse = self
+            .query(&QueryMsg::Balance {
+                owner: owner.to_string(),
+            })
+            .unwrap();
+        res.balance
+    }
+
+    /// Bank balance of `address` in `MINT_DENOM`
+    pub fn bank_balance(&self, address: &Addr) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, MINT_DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    }
+}
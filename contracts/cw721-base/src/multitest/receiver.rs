@@ -0,0 +1,75 @@
+//! Contract receiving `SendNft`, `BatchSendNft` and hook notifications from a
+//! collection. It records what it received as attributes, or rejects
+//! everything when instantiated with `fail`.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+use cw721::Cw721ReceiveMsg;
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+
+use crate::{Cw721BatchReceiveMsg, Cw721HookMsg};
+
+const FAIL: Item<bool> = Item::new("fail");
+
+#[cw_serde]
+pub struct ReceiverInstantiateMsg {
+    pub fail: bool,
+}
+
+#[cw_serde]
+pub enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+    BatchReceiveNft(Cw721BatchReceiveMsg),
+    Cw721Hook(Cw721HookMsg),
+}
+
+pub fn receiver_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ReceiverInstantiateMsg,
+) -> StdResult<Response> {
+    FAIL.save(deps.storage, &msg.fail)?;
+    Ok(Response::new())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ReceiverExecuteMsg,
+) -> StdResult<Response> {
+    if FAIL.load(deps.storage)? {
+        return Err(StdError::generic_err("receiver rejects everything"));
+    }
+
+    let res = Response::new().add_attribute("collection", info.sender);
+    match msg {
+        ReceiverExecuteMsg::ReceiveNft(msg) => Ok(res
+            .add_attribute("action", "receive_nft")
+            .add_attribute("sender", msg.sender)
+            .add_attribute("token_id", msg.token_id)),
+        ReceiverExecuteMsg::BatchReceiveNft(msg) => Ok(res
+            .add_attribute("action", "batch_receive_nft")
+            .add_attribute("sender", msg.sender)
+            .add_attribute("token_ids", msg.token_ids.join(","))),
+        ReceiverExecuteMsg::Cw721Hook(msg) => {
+            let hook = match msg {
+                Cw721HookMsg::Transfer { .. } => "transfer",
+                Cw721HookMsg::Mint { .. } => "mint",
+            };
+            Ok(res
+                .add_attribute("action", "cw721_hook")
+                .add_attribute("hook", hook))
+        }
+    }
+}
+
+fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("receiver has no queries"))
+}
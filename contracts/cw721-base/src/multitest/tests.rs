@@ -0,0 +1,706 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Binary, Empty};
+use cw721::{Expiration, OwnerOfResponse};
+use cw_multi_test::{AppResponse, Executor};
+use cw_ownable::OwnershipError;
+
+use super::*;
+use crate::msg::{
+    HookErrorPolicy, RaffleDrawResponse, RaffleEntryResponse, RaffleRefundResponse, RaffleStatus,
+    RaffleWinnersResponse, SourceTokenClaimResponse,
+};
+use crate::ContractError;
+
+fn has_attribute(res: &AppResponse, key: &str, value: &str) -> bool {
+    res.events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .any(|attr| attr.key == key && attr.value == value)
+}
+
+#[test]
+fn mint_phases_collect_funds() {
+    let mut suite = Suite::new();
+    let (alice, bob) = (suite.user(0), suite.user(1));
+    suite.add_to_og_list(&[(&alice, 1)]).unwrap();
+    suite.add_to_allowlist(&[(&bob, 2)]).unwrap();
+
+    // nothing is open yet
+    let err = suite.mint(&alice, Phase::Public, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PublicSaleClosed {}
+    );
+
+    suite.set_phase(Phase::Og).unwrap();
+    suite.mint(&alice, Phase::Og, 1).unwrap();
+    let err = suite.mint(&bob, Phase::Og, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotOnOgList {}
+    );
+
+    suite.set_phase(Phase::Allowlist).unwrap();
+    let err = suite.mint(&alice, Phase::Og, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::OgSaleClosed {}
+    );
+    suite.mint(&bob, Phase::Allowlist, 2).unwrap();
+
+    suite.set_phase(Phase::Public).unwrap();
+    let err = suite
+        .mint_with_funds(&alice, Phase::Public, 2, &coins(PRICE, MINT_DENOM))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InsufficientFunds {}
+    );
+    suite.mint(&alice, Phase::Public, 2).unwrap();
+
+    // payments moved from the minters to the collection
+    assert_eq!(suite.num_tokens(), 5);
+    assert_eq!(suite.nft_balance(&alice), 3);
+    assert_eq!(suite.nft_balance(&bob), 2);
+    assert_eq!(suite.bank_balance(&alice), INITIAL_BALANCE - 3 * PRICE);
+    assert_eq!(suite.bank_balance(&bob), INITIAL_BALANCE - 2 * PRICE);
+    let collection = suite.collection.clone();
+    assert_eq!(suite.bank_balance(&collection), 5 * PRICE);
+}
+
+#[test]
+fn withdraw_funds_pays_withdraw_address() {
+    let mut suite = Suite::new();
+    let (alice, carol) = (suite.user(0), suite.user(2));
+    suite.set_phase(Phase::Public).unwrap();
+    suite.mint(&alice, Phase::Public, 4).unwrap();
+
+    // the instantiator is paid until a withdraw address is set
+    let owner = suite.owner.clone();
+    suite.withdraw_funds(PRICE).unwrap();
+    assert_eq!(suite.bank_balance(&owner), INITIAL_BALANCE + PRICE);
+
+    // the new address is only paid once the timelock has passed
+    suite.set_withdraw_address(&carol).unwrap();
+    suite.withdraw_funds(PRICE).unwrap();
+    assert_eq!(suite.bank_balance(&owner), INITIAL_BALANCE + 2 * PRICE);
+    suite.apply_pending_changes().unwrap();
+    suite.withdraw_funds(PRICE).unwrap();
+    assert_eq!(suite.bank_balance(&carol), INITIAL_BALANCE + PRICE);
+    let collection = suite.collection.clone();
+    assert_eq!(suite.bank_balance(&collection), PRICE);
+
+    // the bank rejects withdrawing more than the collection holds
+    suite.withdraw_funds(2 * PRICE).unwrap_err();
+    assert_eq!(suite.bank_balance(&collection), PRICE);
+}
+
+#[test]
+fn send_nft_calls_receiver() {
+    let mut suite = Suite::new();
+    let owner = suite.owner.clone();
+    suite.mint_team(2).unwrap();
+    let receiver = suite.instantiate_receiver(false);
+    let rejecting = suite.instantiate_receiver(true);
+
+    let res = suite
+        .send(&owner, &receiver, "0", to_json_binary("hi").unwrap())
+        .unwrap();
+    assert!(has_attribute(&res, "action", "receive_nft"));
+    assert!(has_attribute(&res, "sender", OWNER));
+    assert_eq!(suite.owner_of("0"), receiver.to_string());
+
+    // a failing callback reverts the transfer
+    suite
+        .send(&owner, &rejecting, "1", to_json_binary("hi").unwrap())
+        .unwrap_err();
+    assert_eq!(suite.owner_of("1"), OWNER);
+    assert_eq!(suite.nft_balance(&owner), 1);
+}
+
+#[test]
+fn hooks_follow_error_policy() {
+    let mut suite = Suite::new();
+    let (owner, alice) = (suite.owner.clone(), suite.user(0));
+    let listener = suite.instantiate_receiver(false);
+    let rejecting = suite.instantiate_receiver(true);
+
+    suite.add_hook(&listener, HookErrorPolicy::Abort).unwrap();
+    let res = suite.mint_team(1).unwrap();
+    assert!(has_attribute(&res, "hook", "mint"));
+
+    // an aborting hook that fails blocks the transfer
+    suite.add_hook(&rejecting, HookErrorPolicy::Abort).unwrap();
+    suite.transfer(&owner, &alice, "0").unwrap_err();
+    assert_eq!(suite.owner_of("0"), OWNER);
+
+    // an ignored failure is recorded and the transfer goes through
+    suite.add_hook(&rejecting, HookErrorPolicy::Ignore).unwrap();
+    let res = suite.transfer(&owner, &alice, "0").unwrap();
+    assert!(has_attribute(&res, "hook", "transfer"));
+    assert!(has_attribute(&res, "action", "hook_failed"));
+    assert_eq!(suite.owner_of("0"), alice.to_string());
+}
+
+#[test]
+fn approvals_expire_with_time() {
+    let mut suite = Suite::new();
+    let (owner, alice, bob) = (suite.owner.clone(), suite.user(0), suite.user(1));
+    suite.mint_team(2).unwrap();
+
+    let expires = Expiration::AtTime(suite.block_time().plus_seconds(60));
+    for token_id in ["0", "1"] {
+        let msg = ExecuteMsg::<Extension, Empty>::Approve {
+            spender: alice.to_string(),
+            token_id: token_id.to_string(),
+            expires: Some(expires),
+        };
+        suite.execute(&owner, &msg, &[]).unwrap();
+    }
+
+    suite.advance_seconds(30);
+    suite.transfer(&alice, &bob, "0").unwrap();
+    assert_eq!(suite.owner_of("0"), bob.to_string());
+
+    suite.advance_blocks(6);
+    let err = suite.transfer(&alice, &bob, "1").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Ownership(OwnershipError::NotOwner)
+    );
+    assert_eq!(suite.owner_of("1"), OWNER);
+    assert_eq!(suite.nft_balance(&Addr::unchecked(OWNER)), 1);
+}
+
+#[test]
+fn holders_claim_once_per_source_token() {
+    let mut suite = Suite::new();
+    let (alice, bob) = (suite.user(0), suite.user(1));
+    let source = suite.instantiate_source_collection(&alice, 6);
+
+    let err = suite.holder_claim(&alice, &["0", "1"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::HolderClaimClosed {}
+    );
+
+    // a claim can't mint more than the collection, so claims can't overflow
+    for mints_per_claim in [COLLECTION_SIZE + 1, 1 << 63, u64::MAX] {
+        let err = suite
+            .set_holder_claim_config(&source, 1, mints_per_claim)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidQuantity {}
+        );
+    }
+
+    // two source tokens claim three tokens
+    suite.set_holder_claim_config(&source, 2, 3).unwrap();
+    let err = suite.holder_claim(&alice, &["0"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidQuantity {}
+    );
+    let res = suite.holder_claim(&alice, &["0", "1", "2", "3"]).unwrap();
+    assert!(has_attribute(&res, "quantity", "6"));
+    assert_eq!(suite.nft_balance(&alice), 6);
+    let collection = suite.collection.clone();
+    assert_eq!(suite.bank_balance(&collection), 0);
+
+    let res: SourceTokenClaimResponse = suite
+        .query(&QueryMsg::SourceTokenClaim {
+            token_id: "1".to_string(),
+        })
+        .unwrap();
+    assert_eq!(res.claimed_by, Some(alice.to_string()));
+    let res: SourceTokenClaimResponse = suite
+        .query(&QueryMsg::SourceTokenClaim {
+            token_id: "4".to_string(),
+        })
+        .unwrap();
+    assert_eq!(res.claimed_by, None);
+
+    // a source token can't be claimed with twice, even by a new holder
+    let err = suite.holder_claim(&alice, &["4", "4"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::SourceTokenClaimed {
+            token_id: "4".to_string()
+        }
+    );
+    let transfer = ExecuteMsg::<Extension, Empty>::TransferNft {
+        recipient: bob.to_string(),
+        token_id: "3".to_string(),
+    };
+    suite
+        .app
+        .execute_contract(alice.clone(), source, &transfer, &[])
+        .unwrap();
+    let err = suite.holder_claim(&bob, &["3", "4"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::SourceTokenClaimed {
+            token_id: "3".to_string()
+        }
+    );
+
+    // only the current holder can claim
+    let err = suite.holder_claim(&bob, &["4", "5"]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotSourceTokenOwner {
+            token_id: "4".to_string()
+        }
+    );
+    assert_eq!(suite.nft_balance(&bob), 0);
+}
+
+#[test]
+fn mint_passes_redeem_for_tokens() {
+    let mut suite = Suite::new();
+    let alice = suite.user(0);
+    let passes = suite.instantiate_source_collection(&alice, 3);
+
+    let err = suite.redeem_mint_pass(&alice, &passes, "0").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnknownMintPass {
+            collection: passes.to_string()
+        }
+    );
+
+    // locked passes stay with the collection
+    suite.set_mint_pass(&passes, 2, false).unwrap();
+    let res = suite.redeem_mint_pass(&alice, &passes, "0").unwrap();
+    assert!(has_attribute(&res, "action", "redeem_mint_pass"));
+    assert_eq!(suite.nft_balance(&alice), 2);
+    assert_eq!(suite.owner_of("1"), alice.to_string());
+    let collection = suite.collection.clone();
+    let res: OwnerOfResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &passes,
+            &QueryMsg::<Empty>::OwnerOf {
+                token_id: "0".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.owner, collection.to_string());
+
+    // passes of a collection that can't burn are locked instead
+    suite.set_mint_pass(&passes, 2, true).unwrap();
+    let res = suite.redeem_mint_pass(&alice, &passes, "1").unwrap();
+    assert!(has_attribute(&res, "action", "mint_pass_locked"));
+    assert_eq!(suite.nft_balance(&alice), 4);
+    let res: OwnerOfResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &passes,
+            &QueryMsg::<Empty>::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.owner, collection.to_string());
+
+    // passes that can be burned are, and an empty msg mints the default extension
+    let burnable = suite.instantiate_upstream_collection(&alice, 1);
+    suite.set_mint_pass(&burnable, 2, true).unwrap();
+    let res = suite
+        .redeem_mint_pass_with(&alice, &burnable, "0", Binary::default())
+        .unwrap();
+    assert!(!has_attribute(&res, "action", "mint_pass_locked"));
+    assert_eq!(suite.nft_balance(&alice), 6);
+    assert_eq!(suite.owner_of("5"), alice.to_string());
+    let tokens: NumTokensResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&burnable, &QueryMsg::<Empty>::NumTokens {})
+        .unwrap();
+    assert_eq!(tokens.count, 0);
+
+    // redeeming respects the collection size
+    suite
+        .set_mint_pass(&passes, COLLECTION_SIZE, false)
+        .unwrap();
+    let err = suite.redeem_mint_pass(&alice, &passes, "2").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxSupplyReached {}
+    );
+    assert_eq!(suite.num_tokens(), 6);
+}
+
+#[test]
+fn raffle_winners_mint_and_entries_are_refunded() {
+    let mut suite = Suite::new();
+    let users = [suite.user(0), suite.user(1), suite.user(2)];
+    let collection = suite.collection.clone();
+    let draw_height = suite.block_height() + 10;
+    suite
+        .set_raffle(PRICE, 2, "entropy", draw_height, draw_height + 100)
+        .unwrap();
+
+    // anything paid above the price is sent back
+    let msg = ExecuteMsg::<Extension, Empty>::EnterRaffle {};
+    suite
+        .execute(&users[0], &msg, &coins(2 * PRICE, MINT_DENOM))
+        .unwrap();
+    assert_eq!(suite.bank_balance(&users[0]), INITIAL_BALANCE - PRICE);
+    for user in &users[1..] {
+        suite.enter_raffle(user).unwrap();
+    }
+    let err = suite.enter_raffle(&users[0]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AlreadyEntered {}
+    );
+    let err = suite.claim_raffle_refund(&users[0]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleNotDrawn {}
+    );
+
+    // the draw waits for the draw height, when entries close
+    let err = suite.draw_raffle("entropy").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleDrawPending { draw_height }
+    );
+    suite.advance_blocks(10);
+    let err = suite.enter_raffle(&suite.owner.clone()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleClosed {}
+    );
+
+    // the draw has to reveal the committed entropy
+    let err = suite.draw_raffle("guess").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidEntropy {}
+    );
+    let res = suite.draw_raffle("entropy").unwrap();
+    assert!(has_attribute(&res, "num_winners", "2"));
+    let err = suite.draw_raffle("entropy").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleClosed {}
+    );
+
+    let res: RaffleWinnersResponse = suite
+        .query(&QueryMsg::RaffleWinners {
+            start_after: None,
+            limit: None,
+            start_at: None,
+            end_before: None,
+            order: None,
+        })
+        .unwrap();
+    assert_eq!(res.winners.len(), 2);
+    let winners: Vec<Addr> = res
+        .winners
+        .iter()
+        .map(|winner| Addr::unchecked(&winner.address))
+        .collect();
+    let loser = users
+        .iter()
+        .find(|user| !winners.contains(user))
+        .unwrap()
+        .clone();
+    let res: RaffleEntryResponse = suite
+        .query(&QueryMsg::RaffleEntry {
+            address: loser.to_string(),
+        })
+        .unwrap();
+    assert_eq!(res.status, Some(RaffleStatus::Lost));
+
+    // entries are escrowed until claimed or refunded
+    let err = suite.withdraw_funds(PRICE).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleEscrow {}
+    );
+
+    for winner in &winners {
+        let err = suite.claim_raffle_refund(winner).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NoRaffleRefund {}
+        );
+        suite.claim_raffle_mint(winner).unwrap();
+        assert_eq!(suite.nft_balance(winner), 1);
+        let err = suite.claim_raffle_mint(winner).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotRaffleWinner {}
+        );
+    }
+    let err = suite.claim_raffle_mint(&loser).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotRaffleWinner {}
+    );
+    suite.withdraw_funds(2 * PRICE).unwrap();
+    let err = suite.withdraw_funds(PRICE).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleEscrow {}
+    );
+
+    let res = suite.claim_raffle_refund(&loser).unwrap();
+    assert!(has_attribute(&res, "amount", &PRICE.to_string()));
+    assert_eq!(suite.bank_balance(&loser), INITIAL_BALANCE);
+    assert_eq!(suite.bank_balance(&collection), 0);
+    let res: RaffleRefundResponse = suite
+        .query(&QueryMsg::RaffleRefund {
+            address: loser.to_string(),
+        })
+        .unwrap();
+    assert!(res.refunded);
+    assert!(res.refundable.is_zero());
+    let err = suite.claim_raffle_refund(&loser).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoRaffleRefund {}
+    );
+}
+
+#[test]
+fn raffle_winners_keep_their_supply() {
+    let mut suite = Suite::new();
+    let users = [suite.user(0), suite.user(1), suite.user(2)];
+    suite.mint_team(COLLECTION_SIZE - 3).unwrap();
+    let draw_height = suite.block_height() + 1;
+    suite
+        .set_raffle(PRICE, 2, "entropy", draw_height, draw_height + 100)
+        .unwrap();
+    for user in &users {
+        suite.enter_raffle(user).unwrap();
+    }
+    suite.advance_blocks(1);
+    suite.draw_raffle("entropy").unwrap();
+    let res: RaffleWinnersResponse = suite
+        .query(&QueryMsg::RaffleWinners {
+            start_after: None,
+            limit: None,
+            start_at: None,
+            end_before: None,
+            order: None,
+        })
+        .unwrap();
+    let winners: Vec<Addr> = res
+        .winners
+        .iter()
+        .map(|winner| Addr::unchecked(&winner.address))
+        .collect();
+    let loser = users
+        .iter()
+        .find(|user| !winners.contains(user))
+        .unwrap()
+        .clone();
+
+    // only the token left over by the winners can be minted
+    suite.set_phase(Phase::Public).unwrap();
+    let err = suite.mint(&loser, Phase::Public, 2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxSupplyReached {}
+    );
+    suite.mint(&loser, Phase::Public, 1).unwrap();
+    let err = suite.mint_team(1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MaxSupplyReached {}
+    );
+
+    for winner in &winners {
+        suite.claim_raffle_mint(winner).unwrap();
+        assert_eq!(suite.nft_balance(winner), 1);
+    }
+    assert_eq!(suite.num_tokens(), COLLECTION_SIZE);
+}
+
+#[test]
+fn raffle_entries_are_refunded_past_the_deadline() {
+    let mut suite = Suite::new();
+    let users = [suite.user(0), suite.user(1), suite.user(2)];
+    let collection = suite.collection.clone();
+    let draw_height = suite.block_height() + 1;
+    let err = suite
+        .set_raffle(PRICE, 2, "entropy", draw_height, draw_height)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidRefundDeadline {}
+    );
+    let refund_deadline = draw_height + 5;
+
+    // a commitment that isn't a sha256 could never be revealed
+    let commitment = raffle::entropy_commitment("entropy");
+    for entropy_commitment in [
+        "",
+        "entropy",
+        &commitment[1..],
+        &commitment.replace('a', "g"),
+    ] {
+        let msg = ExecuteMsg::<Extension, Empty>::SetRaffle {
+            price: Uint128::new(PRICE),
+            winners: 2,
+            entropy_commitment: entropy_commitment.to_string(),
+            draw_height,
+            refund_deadline,
+        };
+        let err = suite.execute(&suite.owner.clone(), &msg, &[]).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidEntropyCommitment {}
+        );
+    }
+    suite
+        .set_raffle(PRICE, 2, "entropy", draw_height, refund_deadline)
+        .unwrap();
+    for user in &users {
+        suite.enter_raffle(user).unwrap();
+    }
+
+    // entries are held while the owner can still draw
+    suite.advance_blocks(5);
+    let err = suite.claim_raffle_refund(&users[0]).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleNotDrawn {}
+    );
+
+    // past the deadline the raffle can't be drawn and every entry is refunded
+    suite.advance_blocks(1);
+    let err = suite.draw_raffle("entropy").unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleExpired { refund_deadline }
+    );
+    for user in &users {
+        let res: RaffleRefundResponse = suite
+            .query(&QueryMsg::RaffleRefund {
+                address: user.to_string(),
+            })
+            .unwrap();
+        assert_eq!(res.refundable.u128(), PRICE);
+        let res = suite.claim_raffle_refund(user).unwrap();
+        assert!(has_attribute(&res, "amount", &PRICE.to_string()));
+        assert_eq!(suite.bank_balance(user), INITIAL_BALANCE);
+        let err = suite.claim_raffle_refund(user).unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NoRaffleRefund {}
+        );
+    }
+    assert_eq!(suite.bank_balance(&collection), 0);
+
+    // the raffle is single use
+    let err = suite
+        .set_raffle(PRICE, 2, "entropy", draw_height + 10, draw_height + 20)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RaffleStarted {}
+    );
+}
+
+#[test]
+fn raffle_draw_depends_on_the_entrants() {
+    // same entropy and heights, one entrant swapped
+    let run_raffle = |entrants: &[Addr]| {
+        let mut suite = Suite::new();
+        let draw_height = suite.block_height() + 1;
+        suite
+            .set_raffle(PRICE, 1, "entropy", draw_height, draw_height + 10)
+            .unwrap();
+        for entrant in entrants {
+            suite.enter_raffle(entrant).unwrap();
+        }
+        suite.advance_blocks(1);
+        suite.draw_raffle("entropy").unwrap();
+        let draw: Option<RaffleDrawResponse> = suite.query(&QueryMsg::RaffleDraw {}).unwrap();
+        let winners: RaffleWinnersResponse = suite
+            .query(&QueryMsg::RaffleWinners {
+                start_after: None,
+                limit: None,
+                start_at: None,
+                end_before: None,
+                order: None,
+            })
+            .unwrap();
+        (draw.unwrap(), winners.winners[0].address.clone())
+    };
+    let suite = Suite::new();
+    let (owner, users) = (suite.owner.clone(), suite.users.clone());
+    let (draw, winner) = run_raffle(&users);
+    let (other_draw, other_winner) = run_raffle(&[users[0].clone(), users[1].clone(), owner]);
+
+    assert_ne!(draw.entries_hash, other_draw.entries_hash);
+    assert_ne!(draw.seed, other_draw.seed);
+    assert_ne!(winner, other_winner);
+}
+
+#[test]
+fn mint_protection_throttles_public_mints() {
+    let mut suite = Suite::new();
+    let (alice, bob) = (suite.user(0), suite.user(1));
+    suite.set_phase(Phase::Public).unwrap();
+    suite.set_mint_protection(true, Some(3), Some(2)).unwrap();
+
+    // contracts can't mint, even when they can pay
+    let bot = suite.instantiate_receiver(false);
+    suite
+        .app
+        .send_tokens(alice.clone(), bot.clone(), &coins(PRICE, MINT_DENOM))
+        .unwrap();
+    let err = suite.mint(&bot, Phase::Public, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ContractMinter {}
+    );
+
+    // the block cap counts tokens across minters
+    suite.mint(&alice, Phase::Public, 2).unwrap();
+    let err = suite.mint(&bob, Phase::Public, 2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BlockMintLimit {
+            max_mints_per_block: 3
+        }
+    );
+    suite.mint(&bob, Phase::Public, 1).unwrap();
+
+    // minters wait between their mints
+    suite.advance_blocks(1);
+    let err = suite.mint(&alice, Phase::Public, 1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MintCooldown { blocks_left: 1 }
+    );
+    suite.advance_blocks(1);
+    suite.mint(&alice, Phase::Public, 3).unwrap();
+    assert_eq!(suite.nft_balance(&alice), 5);
+
+    // protections are optional
+    suite.set_mint_protection(false, None, None).unwrap();
+    suite.mint(&bot, Phase::Public, 1).unwrap();
+    suite.mint(&bob, Phase::Public, 5).unwrap();
+    assert_eq!(suite.num_tokens(), 12);
+
+    // an unbounded cooldown locks out repeat minters
+    suite
+        .set_mint_protection(false, None, Some(u64::MAX))
+        .unwrap();
+    let err = suite.mint(&bob, Phase::Public, 1).unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MintCooldown { .. }
+    ));
+}
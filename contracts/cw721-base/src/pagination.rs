@@ -0,0 +1,65 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Order, StdResult};
+use cw_storage_plus::{Bound, PrimaryKey};
+
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum OrderBy {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<OrderBy> for Order {
+    fn from(order: OrderBy) -> Order {
+        match order {
+            OrderBy::Asc => Order::Ascending,
+            OrderBy::Desc => Order::Descending,
+        }
+    }
+}
+
+/// Where a paginated query starts and stops, and which way it iterates.
+/// `start_after` and `start_at` are the cursor the page begins from, in
+/// iteration order, so descending pages walk backwards from them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageRange {
+    pub start_after: Option<String>,
+    pub start_at: Option<String>,
+    pub end_before: Option<String>,
+    pub order: Option<OrderBy>,
+}
+
+impl PageRange {
+    /// Only a start_after cursor, as in the standard cw721 queries
+    pub fn start_after(start_after: Option<String>) -> Self {
+        PageRange {
+            start_after,
+            ..PageRange::default()
+        }
+    }
+
+    /// Returns the (min, max, order) arguments for a range, using `key` to turn
+    /// the cursors into raw storage keys
+    #[allow(clippy::type_complexity)]
+    pub fn bounds<'a, K: PrimaryKey<'a>>(
+        &self,
+        key: impl Fn(&str) -> StdResult<Vec<u8>>,
+    ) -> StdResult<(Option<Bound<'a, K>>, Option<Bound<'a, K>>, Order)> {
+        let start = match (&self.start_at, &self.start_after) {
+            (Some(start_at), _) => Some(Bound::InclusiveRaw(key(start_at)?)),
+            (None, Some(start_after)) => Some(Bound::ExclusiveRaw(key(start_after)?)),
+            (None, None) => None,
+        };
+        let end = match &self.end_before {
+            Some(end_before) => Some(Bound::ExclusiveRaw(key(end_before)?)),
+            None => None,
+        };
+
+        let order = self.order.unwrap_or_default();
+        Ok(match order {
+            OrderBy::Asc => (start, end, order.into()),
+            OrderBy::Desc => (end, start, order.into()),
+        })
+    }
+}
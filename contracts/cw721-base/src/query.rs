@@ -1,8 +1,10 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::iter::once;
 
 use cosmwasm_std::{
     to_json_binary, Addr, Binary, BlockInfo, CustomMsg, Deps, Env, Order, StdError, StdResult,
+    Storage, Uint128,
 };
 
 use cw721::{
@@ -10,11 +12,19 @@ use cw721::{
     Expiration, NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse,
     OwnerOfResponse, TokensResponse,
 };
-use cw_storage_plus::Bound;
-use cw_utils::maybe_addr;
+use cw_storage_plus::{Bound, Map};
 
-use crate::msg::{AdminResponse, BaseTokenUriResponse, CollectionSizeResponse, QueryMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+use crate::msg::{
+    AdminResponse, Allocation, AllocationResponse, AllocationsResponse, BalanceResponse,
+    BaseTokenUriResponse, CollectionSizeResponse, CompactMintResponse, Holder, HoldersResponse,
+    HookResponse, HooksResponse, MintPass, MintPassesResponse, NftInfoExtension,
+    NumHoldersResponse, OperatorFilterResponse, OperatorRegistryResponse, PendingChange,
+    PendingChangesResponse, QueryMsg, RaffleEntryResponse, RaffleRefundResponse, RaffleResponse,
+    RaffleStatus, RaffleWinner, RaffleWinnersResponse, ReservedSupplyResponse,
+    SourceTokenClaimResponse, TimelockDelayResponse, TokenWithInfo, TokensWithInfoResponse,
+};
+use crate::pagination::{OrderBy, PageRange};
+use crate::state::{numeric_key, token_number, Approval, Cw721Contract, MintBatch, TokenInfo};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 1000;
@@ -36,11 +46,10 @@ where
     }
 
     fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
-        let info = self.tokens.load(deps.storage, &token_id)?;
+        let info = self.load_token(deps.storage, &token_id)?;
         let base_token_uri = self.base_token_uri.load(deps.storage)?;
-        let token_uri = format!("{}/{}", base_token_uri, token_id);
         Ok(NftInfoResponse {
-            token_uri: Some(token_uri),
+            token_uri: Some(token_uri(&base_token_uri, &token_id)),
             extension: info.extension,
         })
     }
@@ -52,7 +61,7 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<OwnerOfResponse> {
-        let info = self.tokens.load(deps.storage, &token_id)?;
+        let info = self.load_token(deps.storage, &token_id)?;
         Ok(OwnerOfResponse {
             owner: info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &info, include_expired),
@@ -101,22 +110,14 @@ where
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<OperatorsResponse> {
-        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start_addr = maybe_addr(deps.api, start_after)?;
-        let start = start_addr.as_ref().map(Bound::exclusive);
-
-        let owner_addr = deps.api.addr_validate(&owner)?;
-        let res: StdResult<Vec<_>> = self
-            .operators
-            .prefix(&owner_addr)
-            .range(deps.storage, start, None, Order::Ascending)
-            .filter(|r| {
-                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
-            })
-            .take(limit)
-            .map(parse_approval)
-            .collect();
-        Ok(OperatorsResponse { operators: res? })
+        self.operators_in_range(
+            deps,
+            env,
+            owner,
+            include_expired,
+            PageRange::start_after(start_after),
+            limit,
+        )
     }
 
     fn approval(
@@ -127,7 +128,7 @@ where
         spender: String,
         include_expired: bool,
     ) -> StdResult<ApprovalResponse> {
-        let token = self.tokens.load(deps.storage, &token_id)?;
+        let token = self.load_token(deps.storage, &token_id)?;
 
         // token owner has absolute approval
         if token.owner == spender {
@@ -166,7 +167,7 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<ApprovalsResponse> {
-        let token = self.tokens.load(deps.storage, &token_id)?;
+        let token = self.load_token(deps.storage, &token_id)?;
         let approvals: Vec<_> = token
             .approvals
             .into_iter()
@@ -187,20 +188,7 @@ where
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
-
-        let owner_addr = deps.api.addr_validate(&owner)?;
-        let tokens: Vec<String> = self
-            .tokens
-            .idx
-            .owner
-            .prefix(owner_addr)
-            .keys(deps.storage, start, None, Order::Ascending)
-            .take(limit)
-            .collect::<StdResult<Vec<_>>>()?;
-
-        Ok(TokensResponse { tokens })
+        self.tokens_in_range(deps, owner, PageRange::start_after(start_after), limit)
     }
 
     fn all_tokens(
@@ -209,17 +197,7 @@ where
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
-
-        let tokens: StdResult<Vec<String>> = self
-            .tokens
-            .range(deps.storage, start, None, Order::Ascending)
-            .take(limit)
-            .map(|item| item.map(|(k, _)| k))
-            .collect();
-
-        Ok(TokensResponse { tokens: tokens? })
+        self.all_tokens_in_range(deps, PageRange::start_after(start_after), limit)
     }
 
     fn all_nft_info(
@@ -229,16 +207,15 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<AllNftInfoResponse<T>> {
-        let info = self.tokens.load(deps.storage, &token_id)?;
+        let info = self.load_token(deps.storage, &token_id)?;
         let base_token_uri = self.base_token_uri.load(deps.storage)?;
-        let token_uri = format!("{}/{}", base_token_uri, token_id);
         Ok(AllNftInfoResponse {
             access: OwnerOfResponse {
                 owner: info.owner.to_string(),
                 approvals: humanize_approvals(&env.block, &info, include_expired),
             },
             info: NftInfoResponse {
-                token_uri: Some(token_uri),
+                token_uri: Some(token_uri(&base_token_uri, &token_id)),
                 extension: info.extension,
             },
         })
@@ -256,7 +233,9 @@ where
         match msg {
             QueryMsg::Admin {} => to_json_binary(&self.admin(deps)?),
             QueryMsg::ContractInfo {} => to_json_binary(&self.contract_info(deps)?),
-            QueryMsg::NftInfo { token_id } => to_json_binary(&self.nft_info(deps, token_id)?),
+            QueryMsg::NftInfo { token_id } => {
+                to_json_binary(&self.extended_nft_info(deps, token_id)?)
+            }
             QueryMsg::OwnerOf {
                 token_id,
                 include_expired,
@@ -269,7 +248,7 @@ where
             QueryMsg::AllNftInfo {
                 token_id,
                 include_expired,
-            } => to_json_binary(&self.all_nft_info(
+            } => to_json_binary(&self.extended_all_nft_info(
                 deps,
                 env,
                 token_id,
@@ -291,12 +270,20 @@ where
                 include_expired,
                 start_after,
                 limit,
-            } => to_json_binary(&self.operators(
+                start_at,
+                end_before,
+                order,
+            } => to_json_binary(&self.operators_in_range(
                 deps,
                 env,
                 owner,
                 include_expired.unwrap_or(false),
-                start_after,
+                PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                },
                 limit,
             )?),
             QueryMsg::NumTokens {} => to_json_binary(&self.num_tokens(deps)?),
@@ -304,9 +291,63 @@ where
                 owner,
                 start_after,
                 limit,
-            } => to_json_binary(&self.tokens(deps, owner, start_after, limit)?),
-            QueryMsg::AllTokens { start_after, limit } => {
-                to_json_binary(&self.all_tokens(deps, start_after, limit)?)
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.tokens_in_range(deps, owner, range, limit)?)
+            }
+            QueryMsg::AllTokens {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.all_tokens_in_range(deps, range, limit)?)
+            }
+            QueryMsg::TokensWithInfo {
+                owner,
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.tokens_with_info(deps, owner, range, limit)?)
+            }
+            QueryMsg::AllTokensWithInfo {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.all_tokens_with_info(deps, range, limit)?)
             }
             QueryMsg::Approval {
                 token_id,
@@ -334,21 +375,703 @@ where
                 to_json_binary(&self.withdraw_address.may_load(deps.storage)?)
             }
             QueryMsg::SaleConfig {} => to_json_binary(&self.sale_config.may_load(deps.storage)?),
+            QueryMsg::HolderClaimConfig {} => {
+                to_json_binary(&self.holder_claim_config.may_load(deps.storage)?)
+            }
+            QueryMsg::SourceTokenClaim { token_id } => {
+                to_json_binary(&self.source_token_claim(deps, token_id)?)
+            }
+            QueryMsg::MintProtection {} => to_json_binary(
+                &self
+                    .mint_protection
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            ),
+            QueryMsg::CompactMint {} => to_json_binary(&CompactMintResponse {
+                enabled: self
+                    .compact_mint
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            }),
+            QueryMsg::ReservedSupply {} => to_json_binary(&ReservedSupplyResponse {
+                reserved_supply: self.reserved_supply(deps.storage)?,
+            }),
             QueryMsg::BaseTokenUri {} => {
                 let base_token_uri = self.base_token_uri.may_load(deps.storage)?;
                 to_json_binary(&BaseTokenUriResponse {
                     base_token_uri: base_token_uri.unwrap_or_default(),
                 })
             }
+            QueryMsg::OperatorFilter {} => to_json_binary(&OperatorFilterResponse {
+                mode: self.operator_filter(deps.storage)?,
+            }),
+            QueryMsg::OperatorRegistry {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.operator_registry(deps, range, limit)?)
+            }
             QueryMsg::CollectionSize {} => {
                 let collection_size = self.collection_size.may_load(deps.storage)?;
                 to_json_binary(&CollectionSizeResponse {
                     collection_size: collection_size.unwrap_or_default(),
                 })
             }
+            QueryMsg::Balance { owner } => to_json_binary(&self.balance_of(deps, owner)?),
+            QueryMsg::Holders {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.holders(deps, range, limit)?)
+            }
+            QueryMsg::NumHolders {} => to_json_binary(&NumHoldersResponse {
+                count: self.num_holders(deps.storage)?,
+            }),
+            QueryMsg::Hooks {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.hooks(deps, range, limit)?)
+            }
+            QueryMsg::Raffle {} => to_json_binary(&self.raffle(deps)?),
+            QueryMsg::RaffleEntry { address } => to_json_binary(&self.raffle_entry(deps, address)?),
+            QueryMsg::RaffleDraw {} => to_json_binary(&self.raffle_draw.may_load(deps.storage)?),
+            QueryMsg::RaffleWinners {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.raffle_winners(deps, range, limit)?)
+            }
+            QueryMsg::RaffleRefund { address } => {
+                to_json_binary(&self.raffle_refund(deps, env, address)?)
+            }
+            QueryMsg::MintPasses {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.mint_passes(deps, range, limit)?)
+            }
+            QueryMsg::Allocation { address } => to_json_binary(&self.allocation(deps, address)?),
+            QueryMsg::OgList {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.allocations(deps, &self.og_list, range, limit)?)
+            }
+            QueryMsg::Allowlist {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.allocations(deps, &self.allowlist, range, limit)?)
+            }
+            QueryMsg::TimelockDelay {} => to_json_binary(&TimelockDelayResponse {
+                seconds: self.timelock_delay(deps.storage)?,
+            }),
+            QueryMsg::PendingChanges {
+                start_after,
+                limit,
+                start_at,
+                end_before,
+                order,
+            } => {
+                let range = PageRange {
+                    start_after,
+                    start_at,
+                    end_before,
+                    order,
+                };
+                to_json_binary(&self.pending_changes(deps, range, limit)?)
+            }
         }
     }
 
+    /// operators with optional range and order
+    pub fn operators_in_range(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired: bool,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let res: StdResult<Vec<_>> = self
+            .operators
+            .prefix(&owner_addr)
+            .range(deps.storage, min, max, order)
+            .filter(|r| {
+                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
+            })
+            .take(limit)
+            .map(parse_approval)
+            .collect();
+        Ok(OperatorsResponse { operators: res? })
+    }
+
+    /// tokens with optional range and order
+    pub fn tokens_in_range(
+        &self,
+        deps: Deps,
+        owner: String,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(numeric_key)?;
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let indexed = self
+            .tokens
+            .idx
+            .owner_number
+            .sub_prefix(owner_addr.clone())
+            .keys(deps.storage, min, max, order);
+        let batched = self.batch_token_ids(deps.storage, Some(owner_addr), &range)?;
+        let tokens = merge_token_ids(indexed, batched, order, limit)?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    /// all_tokens with optional range and order
+    pub fn all_tokens_in_range(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(numeric_key)?;
+
+        let indexed = self.tokens.idx.number.keys(deps.storage, min, max, order);
+        let batched = self.batch_token_ids(deps.storage, None, &range)?;
+        let tokens = merge_token_ids(indexed, batched, order, limit)?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    /// Ids of the tokens still in their mint batch that are within `range`, in
+    /// the order of the range. Only the batches of `owner` are read if set.
+    fn batch_token_ids<'b>(
+        &'b self,
+        storage: &'b dyn Storage,
+        owner: Option<Addr>,
+        range: &PageRange,
+    ) -> StdResult<Box<dyn Iterator<Item = StdResult<String>> + 'b>> {
+        let batches = move |min: Option<Bound<'b, u64>>, max: Option<Bound<'b, u64>>, order| {
+            let batches: Box<dyn Iterator<Item = StdResult<(u64, MintBatch<T>)>>> = match &owner {
+                Some(owner) => Box::new(
+                    self.mint_batches
+                        .idx
+                        .owner
+                        .prefix(owner.clone())
+                        .range(storage, min, max, order),
+                ),
+                None => Box::new(self.mint_batches.range(storage, min, max, order)),
+            };
+            batches
+        };
+
+        // Cursors as (number, token_id), the order of the numeric indexes
+        let cursor = |token_id: &String| (token_number(token_id.as_bytes()), token_id.clone());
+        let (start, start_inclusive) = match (&range.start_at, &range.start_after) {
+            (Some(start_at), _) => (Some(cursor(start_at)), true),
+            (None, start_after) => (start_after.as_ref().map(cursor), false),
+        };
+        let end = range.end_before.as_ref().map(cursor);
+        let start_number = start.as_ref().map(|(number, _)| *number);
+
+        let ids: Box<dyn Iterator<Item = StdResult<u64>>> = match range.order.unwrap_or_default() {
+            OrderBy::Asc => {
+                // The batch holding the start cursor begins before it
+                let first = match start_number {
+                    Some(number) => {
+                        batches(None, Some(Bound::inclusive(number)), Order::Descending)
+                            .next()
+                            .transpose()?
+                            .map_or(number, |(first, _)| first)
+                    }
+                    None => 0,
+                };
+                let ids = batches(Some(Bound::inclusive(first)), None, Order::Ascending).flat_map(
+                    move |item| -> Box<dyn Iterator<Item = StdResult<u64>>> {
+                        match item {
+                            Ok((first, batch)) => {
+                                let from = first.max(start_number.unwrap_or_default());
+                                Box::new((from..batch.end).map(Ok))
+                            }
+                            Err(err) => Box::new(once(Err(err))),
+                        }
+                    },
+                );
+                Box::new(ids)
+            }
+            OrderBy::Desc => {
+                let max = start_number.map(Bound::inclusive);
+                let ids = batches(None, max, Order::Descending).flat_map(
+                    move |item| -> Box<dyn Iterator<Item = StdResult<u64>>> {
+                        match item {
+                            Ok((first, batch)) => {
+                                let to = start_number.map_or(batch.end, |number| {
+                                    batch.end.min(number.saturating_add(1))
+                                });
+                                Box::new((first..to).rev().map(Ok))
+                            }
+                            Err(err) => Box::new(once(Err(err))),
+                        }
+                    },
+                );
+                Box::new(ids)
+            }
+        };
+
+        let descending = range.order.unwrap_or_default() == OrderBy::Desc;
+        // Whether `key` comes after `cursor` in the order of the range
+        let after = move |key: &(u64, String), cursor: &(u64, String)| {
+            if descending {
+                key < cursor
+            } else {
+                key > cursor
+            }
+        };
+        let ids = ids
+            .map(|item| item.map(|number| (number, number.to_string())))
+            .filter(move |item| match (item, &start) {
+                (Ok(key), Some(start)) => after(key, start) || (start_inclusive && key == start),
+                _ => true,
+            })
+            .take_while(move |item| match (item, &end) {
+                (Ok(key), Some(end)) => after(end, key),
+                _ => true,
+            })
+            .map(|item| item.map(|(_, token_id)| token_id));
+        Ok(Box::new(ids))
+    }
+
+    pub fn operator_registry(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorRegistryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let operators: StdResult<Vec<String>> = self
+            .operator_registry
+            .keys(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| item.map(|addr| addr.into_string()))
+            .collect();
+
+        Ok(OperatorRegistryResponse {
+            operators: operators?,
+        })
+    }
+
+    pub fn balance_of(&self, deps: Deps, owner: String) -> StdResult<BalanceResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        Ok(BalanceResponse {
+            balance: self.balance(deps.storage, &owner_addr)?,
+        })
+    }
+
+    pub fn holders(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<HoldersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let holders: StdResult<Vec<Holder>> = self
+            .balances
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| {
+                let (address, balance) = item?;
+                Ok(Holder {
+                    address: address.into_string(),
+                    balance,
+                })
+            })
+            .collect();
+
+        Ok(HoldersResponse { holders: holders? })
+    }
+
+    pub fn hooks(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<HooksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let hooks: StdResult<Vec<HookResponse>> = self
+            .hooks
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| {
+                let (contract, error_policy) = item?;
+                Ok(HookResponse {
+                    contract: contract.into_string(),
+                    error_policy,
+                })
+            })
+            .collect();
+
+        Ok(HooksResponse { hooks: hooks? })
+    }
+
+    pub fn mint_passes(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<MintPassesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let mint_passes: StdResult<Vec<MintPass>> = self
+            .mint_passes
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| {
+                let (collection, config) = item?;
+                Ok(MintPass {
+                    collection: collection.into_string(),
+                    mints_per_pass: config.mints_per_pass,
+                    burn: config.burn,
+                })
+            })
+            .collect();
+
+        Ok(MintPassesResponse {
+            mint_passes: mint_passes?,
+        })
+    }
+
+    pub fn raffle(&self, deps: Deps) -> StdResult<Option<RaffleResponse>> {
+        let config = match self.raffle_config.may_load(deps.storage)? {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        Ok(Some(RaffleResponse {
+            config,
+            num_entries: self.raffle_num_entries(deps.storage)?,
+            drawn: self.raffle_draw.exists(deps.storage),
+        }))
+    }
+
+    pub fn raffle_entry(&self, deps: Deps, address: String) -> StdResult<RaffleEntryResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let status = self.raffle_entrants.may_load(deps.storage, &address)?;
+        // entries left after the draw didn't win
+        let status = match status {
+            Some(RaffleStatus::Entered) if self.raffle_draw.exists(deps.storage) => {
+                Some(RaffleStatus::Lost)
+            }
+            status => status,
+        };
+        Ok(RaffleEntryResponse { status })
+    }
+
+    pub fn raffle_winners(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<RaffleWinnersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let winners: StdResult<Vec<RaffleWinner>> = self
+            .raffle_entrants
+            .range(deps.storage, min, max, order)
+            .filter_map(|item| match item {
+                Ok((address, RaffleStatus::Won)) => Some(Ok((address, false))),
+                Ok((address, RaffleStatus::Claimed)) => Some(Ok((address, true))),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .take(limit)
+            .map(|item| {
+                let (address, claimed) = item?;
+                Ok(RaffleWinner {
+                    address: address.into_string(),
+                    claimed,
+                })
+            })
+            .collect();
+
+        Ok(RaffleWinnersResponse { winners: winners? })
+    }
+
+    pub fn raffle_refund(
+        &self,
+        deps: Deps,
+        env: Env,
+        address: String,
+    ) -> StdResult<RaffleRefundResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let refunded =
+            self.raffle_entrants.may_load(deps.storage, &address)? == Some(RaffleStatus::Refunded);
+        Ok(RaffleRefundResponse {
+            refundable: self.raffle_refundable(deps, &env, &address)?,
+            refunded,
+        })
+    }
+
+    /// The escrowed price is refundable for entries that lost the draw, for
+    /// winners that can't claim since the collection minted out, and for every
+    /// entry once the refund deadline passed without a draw
+    pub fn raffle_refundable(&self, deps: Deps, env: &Env, address: &Addr) -> StdResult<Uint128> {
+        let config = match self.raffle_config.may_load(deps.storage)? {
+            Some(config) => config,
+            None => return Ok(Uint128::zero()),
+        };
+        let closed =
+            self.raffle_draw.exists(deps.storage) || env.block.height >= config.refund_deadline;
+        if !closed {
+            return Ok(Uint128::zero());
+        }
+        let refundable = match self.raffle_entrants.may_load(deps.storage, address)? {
+            Some(RaffleStatus::Entered) => true,
+            Some(RaffleStatus::Won) => {
+                let supply = self.public_supply(deps.storage)?
+                    + self.raffle_reserved_supply(deps.storage)?;
+                self.token_count(deps.storage)? >= supply
+            }
+            _ => false,
+        };
+        Ok(if refundable {
+            config.price
+        } else {
+            Uint128::zero()
+        })
+    }
+
+    pub fn source_token_claim(
+        &self,
+        deps: Deps,
+        token_id: String,
+    ) -> StdResult<SourceTokenClaimResponse> {
+        let claimed_by = match self.holder_claim_config.may_load(deps.storage)? {
+            Some(config) => self
+                .claimed_source_tokens
+                .may_load(deps.storage, (&config.source_collection, &token_id))?,
+            None => None,
+        };
+        Ok(SourceTokenClaimResponse {
+            claimed_by: claimed_by.map(Addr::into_string),
+        })
+    }
+
+    pub fn allocation(&self, deps: Deps, address: String) -> StdResult<AllocationResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        Ok(AllocationResponse {
+            og: self
+                .og_list
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+            allowlist: self
+                .allowlist
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn allocations(
+        &self,
+        deps: Deps,
+        list: &Map<&Addr, u64>,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<AllocationsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(|addr| addr_key(deps, addr))?;
+
+        let allocations: StdResult<Vec<Allocation>> = list
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| {
+                let (address, remaining) = item?;
+                Ok(Allocation {
+                    address: address.into_string(),
+                    remaining,
+                })
+            })
+            .collect();
+
+        Ok(AllocationsResponse {
+            allocations: allocations?,
+        })
+    }
+
+    pub fn pending_changes(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<PendingChangesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let (min, max, order) = range.bounds(change_id_key)?;
+
+        let changes: StdResult<Vec<PendingChange>> = self
+            .pending_changes
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| Ok(item?.1))
+            .collect();
+
+        Ok(PendingChangesResponse { changes: changes? })
+    }
+
+    /// nft_info with the contract-managed token flags added to the extension
+    pub fn extended_nft_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+    ) -> StdResult<NftInfoResponse<NftInfoExtension<T>>> {
+        let transferable = self.is_transferable(deps.storage, &token_id)?;
+        let info = self.nft_info(deps, token_id)?;
+        Ok(extend_nft_info(info, transferable))
+    }
+
+    /// all_nft_info with the contract-managed token flags added to the extension
+    pub fn extended_all_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<NftInfoExtension<T>>> {
+        let transferable = self.is_transferable(deps.storage, &token_id)?;
+        let res = self.all_nft_info(deps, env, token_id, include_expired)?;
+        Ok(AllNftInfoResponse {
+            access: res.access,
+            info: extend_nft_info(res.info, transferable),
+        })
+    }
+
+    /// tokens with the owner, uri and extension of each token
+    pub fn tokens_with_info(
+        &self,
+        deps: Deps,
+        owner: String,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<NftInfoExtension<T>>> {
+        let tokens = self.tokens_in_range(deps, owner, range, limit)?;
+        self.with_info(deps, tokens.tokens)
+    }
+
+    /// all_tokens with the owner, uri and extension of each token
+    pub fn all_tokens_with_info(
+        &self,
+        deps: Deps,
+        range: PageRange,
+        limit: Option<u32>,
+    ) -> StdResult<TokensWithInfoResponse<NftInfoExtension<T>>> {
+        let tokens = self.all_tokens_in_range(deps, range, limit)?;
+        self.with_info(deps, tokens.tokens)
+    }
+
+    fn with_info(
+        &self,
+        deps: Deps,
+        token_ids: Vec<String>,
+    ) -> StdResult<TokensWithInfoResponse<NftInfoExtension<T>>> {
+        let base_token_uri = self.base_token_uri.load(deps.storage)?;
+        let tokens: StdResult<Vec<_>> = token_ids
+            .into_iter()
+            .map(|token_id| {
+                let info = self.load_token(deps.storage, &token_id)?;
+                let transferable = self.is_transferable(deps.storage, &token_id)?;
+                Ok(TokenWithInfo {
+                    token_uri: Some(token_uri(&base_token_uri, &token_id)),
+                    owner: info.owner.into_string(),
+                    extension: NftInfoExtension {
+                        transferable,
+                        extension: info.extension,
+                    },
+                    token_id,
+                })
+            })
+            .collect();
+
+        Ok(TokensWithInfoResponse { tokens: tokens? })
+    }
+
     pub fn admin(&self, deps: Deps) -> StdResult<AdminResponse> {
         let admin = cw_ownable::get_ownership(deps.storage)?
             .owner
@@ -362,6 +1085,67 @@ where
     }
 }
 
+/// Merges two lists of token ids sorted in `order` of the numeric indexes, up to `limit`
+fn merge_token_ids(
+    indexed: impl Iterator<Item = StdResult<String>>,
+    batched: impl Iterator<Item = StdResult<String>>,
+    order: Order,
+    limit: usize,
+) -> StdResult<Vec<String>> {
+    let key = |token_id: &String| (token_number(token_id.as_bytes()), token_id.clone());
+    let mut indexed = indexed.peekable();
+    let mut batched = batched.peekable();
+    let mut tokens = vec![];
+    while tokens.len() < limit {
+        let take_indexed = match (indexed.peek(), batched.peek()) {
+            (None, None) => break,
+            (Some(_), None) | (Some(Err(_)), _) => true,
+            (None, Some(_)) | (_, Some(Err(_))) => false,
+            (Some(Ok(a)), Some(Ok(b))) => match order {
+                Order::Ascending => key(a) < key(b),
+                Order::Descending => key(a) > key(b),
+            },
+        };
+        let next = if take_indexed {
+            indexed.next()
+        } else {
+            batched.next()
+        };
+        if let Some(token_id) = next {
+            tokens.push(token_id?);
+        }
+    }
+    Ok(tokens)
+}
+
+fn addr_key(deps: Deps, addr: &str) -> StdResult<Vec<u8>> {
+    Ok(deps.api.addr_validate(addr)?.as_bytes().to_vec())
+}
+
+fn change_id_key(id: &str) -> StdResult<Vec<u8>> {
+    let id: u64 = id
+        .parse()
+        .map_err(|_| StdError::parse_err("u64", format!("invalid change id: {id}")))?;
+    Ok(id.to_be_bytes().to_vec())
+}
+
+pub(crate) fn token_uri(base_token_uri: &str, token_id: &str) -> String {
+    format!("{}/{}", base_token_uri, token_id)
+}
+
+fn extend_nft_info<T>(
+    info: NftInfoResponse<T>,
+    transferable: bool,
+) -> NftInfoResponse<NftInfoExtension<T>> {
+    NftInfoResponse {
+        token_uri: info.token_uri,
+        extension: NftInfoExtension {
+            transferable,
+            extension: info.extension,
+        },
+    }
+}
+
 fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<cw721::Approval> {
     item.map(|(spender, expires)| cw721::Approval {
         spender: spender.to_string(),
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+/// Lowercase hex encoding of `bytes`
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hex encoded sha256 of the entropy, as committed to when the raffle is set
+pub fn entropy_commitment(entropy: &str) -> String {
+    to_hex(&Sha256::digest(entropy.as_bytes()))
+}
+
+/// Folds an entrant into the hash of the entries before it
+pub fn chain_entry(entries_hash: &[u8; 32], entrant: &str) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(entries_hash)
+        .chain_update(entrant.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Whether `commitment` is a hex encoded sha256, as `entropy_commitment` returns
+pub fn is_commitment(commitment: &str) -> bool {
+    commitment.len() == 64 && commitment.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// Seed of a draw, mixing the revealed entropy with the draw height committed to
+/// with it, so the outcome doesn't depend on the block the draw lands in. The
+/// hash of the entries is only known once entries close, so the owner can't
+/// work out the winning entries when committing to the entropy.
+pub fn draw_seed(
+    entropy: &str,
+    entries_hash: &[u8; 32],
+    chain_id: &str,
+    draw_height: u64,
+) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(entropy.as_bytes())
+        .chain_update(entries_hash)
+        .chain_update(draw_height.to_be_bytes())
+        .chain_update(chain_id.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Picks `winners` distinct entry indexes out of `entries` with a partial
+/// Fisher-Yates shuffle, only tracking the positions that were swapped
+pub fn draw_winners(seed: &[u8; 32], entries: u64, winners: u64) -> Vec<u64> {
+    let mut swapped = BTreeMap::new();
+    (0..winners.min(entries))
+        .map(|i| {
+            let hash = Sha256::new()
+                .chain_update(seed)
+                .chain_update(i.to_be_bytes())
+                .finalize();
+            let mut random = [0u8; 8];
+            random.copy_from_slice(&hash[..8]);
+            let j = i + u64::from_be_bytes(random) % (entries - i);
+
+            let picked = *swapped.get(&j).unwrap_or(&j);
+            let current = *swapped.get(&i).unwrap_or(&i);
+            swapped.insert(j, current);
+            picked
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+
+/// Cw721BatchReceiveMsg should be de/serialized under `BatchReceiveNft()` variant in a ExecuteMsg
+#[cw_serde]
+pub struct Cw721BatchReceiveMsg {
+    pub sender: String,
+    pub token_ids: Vec<String>,
+    pub msg: Binary,
+}
+
+impl Cw721BatchReceiveMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::BatchReceiveNft(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>, C>(self, contract_addr: T) -> StdResult<CosmosMsg<C>>
+    where
+        C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+/// This is just a helper to properly serialize the above message.
+/// The actual receiver should include this variant in the larger ExecuteMsg enum
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    BatchReceiveNft(Cw721BatchReceiveMsg),
+}
@@ -1,14 +1,22 @@
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::any::type_name;
 use std::marker::PhantomData;
 
-use cosmwasm_std::{Addr, BlockInfo, CustomMsg, StdResult, Storage};
+use cosmwasm_std::{Addr, BlockInfo, CustomMsg, Order, StdError, StdResult, Storage, Uint128};
 
 use cw721::{ContractInfoResponse, Cw721, Expiration};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, PrimaryKey};
 
-use crate::msg::SaleConfigResponse;
+use crate::msg::{
+    HolderClaimConfigResponse, HookErrorPolicy, MintPassConfig, MintProtectionResponse,
+    OperatorFilterMode, PendingChange, RaffleConfig, RaffleDrawResponse, RaffleStatus,
+    SaleConfigResponse,
+};
+
+/// Seconds owner changes are queued for until the owner sets another delay
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 2 * 24 * 60 * 60;
 
 pub struct Cw721Contract<'a, T, C, E, Q>
 where
@@ -19,14 +27,67 @@ where
     pub contract_info: Item<'a, ContractInfoResponse>,
     pub token_count: Item<'a, u64>,
     pub collection_size: Item<'a, u64>,
+    /// What's left of the supply reserved for team mints
+    pub reserved_supply: Item<'a, u64>,
     pub base_token_uri: Item<'a, String>,
     /// Stored as (granter, operator) giving operator full control over granter's account
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
     pub withdraw_address: Item<'a, String>,
     pub sale_config: Item<'a, SaleConfigResponse>,
-    pub allowlist: Map<'a, &'a Addr, bool>,
-    pub og_list: Map<'a, &'a Addr, bool>,
+    /// Tokens each address can still mint from the allowlist
+    pub allowlist: Map<'a, &'a Addr, u64>,
+    /// Tokens each address can still mint from the og list
+    pub og_list: Map<'a, &'a Addr, u64>,
+    /// Collection-wide transferability, tokens are transferable if unset
+    pub transferable: Item<'a, bool>,
+    /// Per-token transferability, overrides the collection-wide flag
+    pub token_transferable: Map<'a, &'a str, bool>,
+    /// How the operator registry is applied to approvals and third-party transfers
+    pub operator_filter: Item<'a, OperatorFilterMode>,
+    /// Operators allowed or blocked, depending on the operator filter mode
+    pub operator_registry: Map<'a, &'a Addr, bool>,
+    /// Number of tokens held by each address, holders are removed once they hold none
+    pub balances: Map<'a, &'a Addr, u64>,
+    /// Number of addresses in balances
+    pub num_holders: Item<'a, u64>,
+    /// Contracts notified of transfers and mints
+    pub hooks: Map<'a, &'a Addr, HookErrorPolicy>,
+    /// Seconds owner changes are queued for, `DEFAULT_TIMELOCK_DELAY` if unset
+    pub timelock_delay: Item<'a, u64>,
+    /// Changes waiting for their timelock to pass, by id
+    pub pending_changes: Map<'a, u64, PendingChange>,
+    /// Id of the last queued change
+    pub last_change_id: Item<'a, u64>,
+    /// Collection whose holders can claim free mints, claims are closed if unset
+    pub holder_claim_config: Item<'a, HolderClaimConfigResponse>,
+    /// Address that claimed with each (source collection, source token id)
+    pub claimed_source_tokens: Map<'a, (&'a Addr, &'a str), Addr>,
+    /// Collections whose passes can be sent in to be redeemed for mints
+    pub mint_passes: Map<'a, &'a Addr, MintPassConfig>,
+    pub raffle_config: Item<'a, RaffleConfig>,
+    /// Raffle entrants by entry index, from 0 to the number of entries
+    pub raffle_entries: Map<'a, u64, Addr>,
+    /// Raffle status of each entrant, entrants that lost the draw stay `Entered`
+    pub raffle_entrants: Map<'a, &'a Addr, RaffleStatus>,
+    /// Hash chain of the raffle entrants in entry order, mixed into the draw seed
+    pub raffle_entries_hash: Item<'a, [u8; 32]>,
+    pub raffle_draw: Item<'a, RaffleDrawResponse>,
+    /// Raffle payments held for winners to claim or entrants to be refunded
+    pub raffle_escrow: Item<'a, Uint128>,
+    /// Supply held for raffle winners until they claim their token or are refunded
+    pub raffle_reserved_supply: Item<'a, u64>,
+    /// Public mint protections, all off if unset
+    pub mint_protection: Item<'a, MintProtectionResponse>,
+    /// Height of the last block with public mints and how many tokens it minted
+    pub block_mints: Item<'a, (u64, u64)>,
+    /// Height of the last public mint of each address
+    pub last_mint_heights: Map<'a, &'a Addr, u64>,
+    /// Mints tokens as batch records instead of a `TokenInfo` each, if set
+    pub compact_mint: Item<'a, bool>,
+    /// Consecutive tokens minted together in compact mode, by first token id.
+    /// Tokens leave their batch for `tokens` on their first transfer or approval.
+    pub mint_batches: IndexedMap<'a, u64, MintBatch<T>, MintBatchIndexes<'a, T>>,
 
     pub(crate) _custom_response: PhantomData<C>,
     pub(crate) _custom_query: PhantomData<Q>,
@@ -54,14 +115,43 @@ where
             "nft_info",
             "num_tokens",
             "collection_size",
+            "reserved_supply",
             "base_token_uri",
             "operators",
             "tokens",
             "tokens__owner",
+            "tokens__number",
+            "tokens__owner_number",
             "withdraw_address",
             "sale_config",
             "allowlist",
             "og_list_key",
+            "transferable",
+            "token_transferable",
+            "operator_filter",
+            "operator_registry",
+            "balances",
+            "num_holders",
+            "hooks",
+            "timelock_delay",
+            "pending_changes",
+            "last_change_id",
+            "holder_claim_config",
+            "claimed_source_tokens",
+            "mint_passes",
+            "raffle_config",
+            "raffle_entries",
+            "raffle_entrants",
+            "raffle_entries_hash",
+            "raffle_draw",
+            "raffle_escrow",
+            "raffle_reserved_supply",
+            "mint_protection",
+            "block_mints",
+            "last_mint_heights",
+            "compact_mint",
+            "mint_batches",
+            "mint_batches__owner",
         )
     }
 }
@@ -77,22 +167,65 @@ where
         contract_key: &'a str,
         token_count_key: &'a str,
         collection_size_key: &'a str,
+        reserved_supply_key: &'a str,
         base_token_uri_key: &'a str,
         operator_key: &'a str,
         tokens_key: &'a str,
         tokens_owner_key: &'a str,
+        tokens_number_key: &'a str,
+        tokens_owner_number_key: &'a str,
         withdraw_address_key: &'a str,
         sale_config_key: &'a str,
         allowlist_key: &'a str,
         og_list_key: &'a str,
+        transferable_key: &'a str,
+        token_transferable_key: &'a str,
+        operator_filter_key: &'a str,
+        operator_registry_key: &'a str,
+        balances_key: &'a str,
+        num_holders_key: &'a str,
+        hooks_key: &'a str,
+        timelock_delay_key: &'a str,
+        pending_changes_key: &'a str,
+        last_change_id_key: &'a str,
+        holder_claim_config_key: &'a str,
+        claimed_source_tokens_key: &'a str,
+        mint_passes_key: &'a str,
+        raffle_config_key: &'a str,
+        raffle_entries_key: &'a str,
+        raffle_entrants_key: &'a str,
+        raffle_entries_hash_key: &'a str,
+        raffle_draw_key: &'a str,
+        raffle_escrow_key: &'a str,
+        raffle_reserved_supply_key: &'a str,
+        mint_protection_key: &'a str,
+        block_mints_key: &'a str,
+        last_mint_heights_key: &'a str,
+        compact_mint_key: &'a str,
+        mint_batches_key: &'a str,
+        mint_batches_owner_key: &'a str,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, tokens_key, tokens_owner_key),
+            number: MultiIndex::new(token_number_idx, tokens_key, tokens_number_key),
+            owner_number: MultiIndex::new(
+                token_owner_number_idx,
+                tokens_key,
+                tokens_owner_number_key,
+            ),
+        };
+        let batch_indexes = MintBatchIndexes {
+            owner: MultiIndex::new(
+                mint_batch_owner_idx,
+                mint_batches_key,
+                mint_batches_owner_key,
+            ),
         };
         Self {
             contract_info: Item::new(contract_key),
             token_count: Item::new(token_count_key),
             collection_size: Item::new(collection_size_key),
+            reserved_supply: Item::new(reserved_supply_key),
             base_token_uri: Item::new(base_token_uri_key),
             operators: Map::new(operator_key),
             tokens: IndexedMap::new(tokens_key, indexes),
@@ -100,12 +233,153 @@ where
             sale_config: Item::new(sale_config_key),
             allowlist: Map::new(allowlist_key),
             og_list: Map::new(og_list_key),
+            transferable: Item::new(transferable_key),
+            token_transferable: Map::new(token_transferable_key),
+            operator_filter: Item::new(operator_filter_key),
+            operator_registry: Map::new(operator_registry_key),
+            balances: Map::new(balances_key),
+            num_holders: Item::new(num_holders_key),
+            hooks: Map::new(hooks_key),
+            timelock_delay: Item::new(timelock_delay_key),
+            pending_changes: Map::new(pending_changes_key),
+            last_change_id: Item::new(last_change_id_key),
+            holder_claim_config: Item::new(holder_claim_config_key),
+            claimed_source_tokens: Map::new(claimed_source_tokens_key),
+            mint_passes: Map::new(mint_passes_key),
+            raffle_config: Item::new(raffle_config_key),
+            raffle_entries: Map::new(raffle_entries_key),
+            raffle_entrants: Map::new(raffle_entrants_key),
+            raffle_entries_hash: Item::new(raffle_entries_hash_key),
+            raffle_draw: Item::new(raffle_draw_key),
+            raffle_escrow: Item::new(raffle_escrow_key),
+            raffle_reserved_supply: Item::new(raffle_reserved_supply_key),
+            mint_protection: Item::new(mint_protection_key),
+            block_mints: Item::new(block_mints_key),
+            last_mint_heights: Map::new(last_mint_heights_key),
+            compact_mint: Item::new(compact_mint_key),
+            mint_batches: IndexedMap::new(mint_batches_key, batch_indexes),
             _custom_response: PhantomData,
             _custom_execute: PhantomData,
             _custom_query: PhantomData,
         }
     }
 
+    pub fn raffle_num_entries(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self
+            .raffle_entries
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1))
+    }
+
+    pub fn raffle_escrow(&self, storage: &dyn Storage) -> StdResult<Uint128> {
+        Ok(self.raffle_escrow.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn reserved_supply(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.reserved_supply.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn raffle_reserved_supply(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self
+            .raffle_reserved_supply
+            .may_load(storage)?
+            .unwrap_or_default())
+    }
+
+    /// Collection size left to every mint but team mints and raffle claims, which
+    /// draw down their reservations
+    pub fn public_supply(&self, storage: &dyn Storage) -> StdResult<u64> {
+        let collection_size = self.collection_size.load(storage)?;
+        let reserved = self.reserved_supply(storage)? + self.raffle_reserved_supply(storage)?;
+        Ok(collection_size.saturating_sub(reserved))
+    }
+
+    /// Token with the given id, wherever it is stored
+    pub fn may_load_token(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> StdResult<Option<TokenInfo<T>>> {
+        if let Some(token) = self.tokens.may_load(storage, token_id)? {
+            return Ok(Some(token));
+        }
+        Ok(self
+            .mint_batch_of(storage, token_id)?
+            .map(|(_, batch)| TokenInfo {
+                owner: batch.owner,
+                approvals: vec![],
+                extension: batch.extension,
+            }))
+    }
+
+    /// Same as `tokens.load`, including tokens still in their mint batch
+    pub fn load_token(&self, storage: &dyn Storage, token_id: &str) -> StdResult<TokenInfo<T>> {
+        self.may_load_token(storage, token_id)?
+            .ok_or_else(|| StdError::not_found(type_name::<TokenInfo<T>>()))
+    }
+
+    /// Mint batch holding the given token id, with the id of its first token
+    pub fn mint_batch_of(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> StdResult<Option<(u64, MintBatch<T>)>> {
+        // Batches only hold ids minted as plain decimal numbers
+        let number = token_number(token_id.as_bytes());
+        if number.to_string() != token_id {
+            return Ok(None);
+        }
+        let batch = self
+            .mint_batches
+            .range(
+                storage,
+                None,
+                Some(Bound::inclusive(number)),
+                Order::Descending,
+            )
+            .next()
+            .transpose()?;
+        Ok(batch.filter(|(_, batch)| number < batch.end))
+    }
+
+    /// Moves a token out of its mint batch into `tokens`, splitting the rest of
+    /// the batch around it. Tokens already in `tokens` are left as they are.
+    pub fn unpack_token(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        let (start, batch) = match self.mint_batch_of(storage, token_id)? {
+            Some(found) => found,
+            None => return Ok(()),
+        };
+        let number = token_number(token_id.as_bytes());
+
+        self.mint_batches.remove(storage, start)?;
+        if start < number {
+            let before = MintBatch {
+                end: number,
+                ..batch.clone()
+            };
+            self.mint_batches.save(storage, start, &before)?;
+        }
+        if number + 1 < batch.end {
+            self.mint_batches.save(storage, number + 1, &batch)?;
+        }
+
+        // The flag the batch was minted with now applies to the token alone
+        if let Some(transferable) = batch.transferable {
+            if !self.token_transferable.has(storage, token_id) {
+                self.token_transferable
+                    .save(storage, token_id, &transferable)?;
+            }
+        }
+        let token = TokenInfo {
+            owner: batch.owner,
+            approvals: vec![],
+            extension: batch.extension,
+        };
+        self.tokens.save(storage, token_id, &token)
+    }
+
     pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
         Ok(self.token_count.may_load(storage)?.unwrap_or_default())
     }
@@ -121,6 +395,68 @@ where
         self.token_count.save(storage, &val)?;
         Ok(val)
     }
+
+    pub fn is_transferable(&self, storage: &dyn Storage, token_id: &str) -> StdResult<bool> {
+        if let Some(transferable) = self.token_transferable.may_load(storage, token_id)? {
+            return Ok(transferable);
+        }
+        let batch = self.mint_batch_of(storage, token_id)?;
+        match batch.and_then(|(_, batch)| batch.transferable) {
+            Some(transferable) => Ok(transferable),
+            None => self.is_collection_transferable(storage),
+        }
+    }
+
+    pub fn is_collection_transferable(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.transferable.may_load(storage)?.unwrap_or(true))
+    }
+
+    pub fn operator_filter(&self, storage: &dyn Storage) -> StdResult<OperatorFilterMode> {
+        Ok(self.operator_filter.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn timelock_delay(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self
+            .timelock_delay
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_TIMELOCK_DELAY))
+    }
+
+    pub fn balance(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<u64> {
+        Ok(self.balances.may_load(storage, owner)?.unwrap_or_default())
+    }
+
+    pub fn num_holders(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.num_holders.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn increment_balance(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        quantity: u64,
+    ) -> StdResult<u64> {
+        let balance = self.balance(storage, owner)?;
+        if balance == 0 && quantity > 0 {
+            let num_holders = self.num_holders(storage)? + 1;
+            self.num_holders.save(storage, &num_holders)?;
+        }
+        let val = balance + quantity;
+        self.balances.save(storage, owner, &val)?;
+        Ok(val)
+    }
+
+    pub fn decrement_balance(&self, storage: &mut dyn Storage, owner: &Addr) -> StdResult<u64> {
+        let val = self.balance(storage, owner)? - 1;
+        if val == 0 {
+            self.balances.remove(storage, owner);
+            let num_holders = self.num_holders(storage)? - 1;
+            self.num_holders.save(storage, &num_holders)?;
+        } else {
+            self.balances.save(storage, owner, &val)?;
+        }
+        Ok(val)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -147,11 +483,48 @@ impl Approval {
     }
 }
 
+/// Tokens `start..end` minted together to one owner, keyed by `start`.
+/// They have no approvals until they're unpacked into `tokens`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintBatch<T> {
+    pub owner: Addr,
+    /// Id after the last token of the batch
+    pub end: u64,
+    pub extension: T,
+    /// Transferable flag the tokens were minted with, if any
+    pub transferable: Option<bool>,
+}
+
+pub struct MintBatchIndexes<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub owner: MultiIndex<'a, Addr, MintBatch<T>, u64>,
+}
+
+impl<'a, T> IndexList<MintBatch<T>> for MintBatchIndexes<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<MintBatch<T>>> + '_> {
+        let v: Vec<&dyn Index<MintBatch<T>>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn mint_batch_owner_idx<T>(_pk: &[u8], d: &MintBatch<T>) -> Addr {
+    d.owner.clone()
+}
+
 pub struct TokenIndexes<'a, T>
 where
     T: Serialize + DeserializeOwned + Clone,
 {
     pub owner: MultiIndex<'a, Addr, TokenInfo<T>, String>,
+    /// Orders tokens by the numeric value of their id
+    pub number: MultiIndex<'a, u64, TokenInfo<T>, String>,
+    /// Orders each owner's tokens by the numeric value of their id
+    pub owner_number: MultiIndex<'a, (Addr, u64), TokenInfo<T>, String>,
 }
 
 impl<'a, T> IndexList<TokenInfo<T>> for TokenIndexes<'a, T>
@@ -159,7 +532,7 @@ where
     T: Serialize + DeserializeOwned + Clone,
 {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo<T>>> + '_> {
-        let v: Vec<&dyn Index<TokenInfo<T>>> = vec![&self.owner];
+        let v: Vec<&dyn Index<TokenInfo<T>>> = vec![&self.owner, &self.number, &self.owner_number];
         Box::new(v.into_iter())
     }
 }
@@ -167,3 +540,25 @@ where
 pub fn token_owner_idx<T>(_pk: &[u8], d: &TokenInfo<T>) -> Addr {
     d.owner.clone()
 }
+
+pub fn token_number_idx<T>(pk: &[u8], _d: &TokenInfo<T>) -> u64 {
+    token_number(pk)
+}
+
+pub fn token_owner_number_idx<T>(pk: &[u8], d: &TokenInfo<T>) -> (Addr, u64) {
+    (d.owner.clone(), token_number(pk))
+}
+
+/// Numeric value of a token id, used to enumerate tokens in mint order.
+/// Ids are always minted as decimal strings, anything else sorts last.
+pub fn token_number(token_id: &[u8]) -> u64 {
+    std::str::from_utf8(token_id)
+        .ok()
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(u64::MAX)
+}
+
+/// Raw key of a token on the numeric token indexes, which are keyed by (number, token_id)
+pub fn numeric_key(token_id: &str) -> StdResult<Vec<u8>> {
+    Ok((token_number(token_id.as_bytes()), token_id.to_string()).joined_key())
+}
@@ -1 +1,89 @@
-pub mod v0_17;
+use cosmwasm_std::{CustomMsg, DepsMut, Response, Storage};
+use semver::Version;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::ContractError;
+
+pub mod upstream_v0_18;
+pub mod v0_2;
+pub mod v0_3;
+
+type Step<C> = fn(DepsMut) -> Result<Response<C>, ContractError>;
+
+/// Upgrade steps in version order. A step runs when migrating from a version
+/// below it to a version at or above it. The workspace version is bumped in its
+/// own commit, or in the commit adding the step for that version, so no commit
+/// has a step ahead of the version it is built as.
+pub(crate) fn steps<T, C, E, Q>() -> Vec<(&'static str, Step<C>)>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    Q: CustomMsg,
+    E: CustomMsg,
+{
+    vec![
+        ("0.2.0", v0_2::migrate::<T, C, E, Q>),
+        ("0.3.0", v0_3::migrate::<T, C, E, Q>),
+    ]
+}
+
+/// Checks the stored cw2 info belongs to `contract` and isn't newer than `to`,
+/// returning the stored version
+pub fn assert_can_migrate(
+    storage: &dyn Storage,
+    contract: &str,
+    to: &Version,
+) -> Result<Version, ContractError> {
+    let stored = cw2::get_contract_version(storage)?;
+    if stored.contract != contract {
+        return Err(cw2::VersionError::WrongContract {
+            expected: contract.to_string(),
+            found: stored.contract,
+        }
+        .into());
+    }
+
+    let from: Version = stored.version.parse()?;
+    if from > *to {
+        return Err(ContractError::CannotDowngrade {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+    Ok(from)
+}
+
+/// Runs every upgrade step between the stored version and `to`, then stores
+/// `to` as the contract version. Each step run is reported as a `step`
+/// attribute followed by the attributes of that step.
+pub fn migrate<T, C, E, Q>(
+    mut deps: DepsMut,
+    contract: &str,
+    to: &str,
+) -> Result<Response<C>, ContractError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    Q: CustomMsg,
+    E: CustomMsg,
+{
+    let to: Version = to.parse()?;
+    let from = assert_can_migrate(deps.storage, contract, &to)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from.to_string())
+        .add_attribute("to_version", to.to_string());
+    for (version, step) in steps::<T, C, E, Q>() {
+        let version: Version = version.parse()?;
+        if from < version && version <= to {
+            let step_res = step(deps.branch())?;
+            res = res
+                .add_attribute("step", version.to_string())
+                .add_attributes(step_res.attributes)
+                .add_submessages(step_res.messages)
+                .add_events(step_res.events);
+        }
+    }
+
+    cw2::set_contract_version(deps.storage, contract, to.to_string())?;
+    Ok(res)
+}
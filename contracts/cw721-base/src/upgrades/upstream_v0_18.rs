@@ -0,0 +1,138 @@
+use cosmwasm_std::{CustomMsg, DepsMut, Order, Response, StdResult};
+use cw721_base_018 as v18;
+use semver::{Version, VersionReq};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::msg::{SaleConfigResponse, UpstreamMigrateMsg};
+use crate::query::token_uri;
+use crate::state::{token_number, Approval, TokenInfo};
+use crate::validation::{validate_base_token_uri, validate_collection_size, validate_sale_config};
+use crate::{ContractError, Cw721Contract};
+
+/// Converts an upstream cw721-base 0.18 store in place. Contract info,
+/// ownership and operators share their storage keys and are kept as they are.
+/// Tokens keep their owner, approvals and extension. Token URIs are served from
+/// the base token URI, so the migration fails if a token stores any other URI.
+pub fn migrate<T, C, E, Q>(
+    deps: DepsMut,
+    contract: &str,
+    to: &str,
+    msg: UpstreamMigrateMsg,
+) -> Result<Response<C>, ContractError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    Q: CustomMsg,
+    E: CustomMsg,
+{
+    let stored = cw2::get_contract_version(deps.storage)?;
+    if stored.contract != contract {
+        return Err(cw2::VersionError::WrongContract {
+            expected: contract.to_string(),
+            found: stored.contract,
+        }
+        .into());
+    }
+    let from: Version = stored.version.parse()?;
+    if !VersionReq::parse("~0.18")?.matches(&from) {
+        return Err(cw2::VersionError::WrongVersion {
+            expected: "0.18".to_string(),
+            found: stored.version,
+        }
+        .into());
+    }
+
+    // move tokens out of the upstream indexes and into ours
+    let tract18 = v18::Cw721Contract::<T, C, E, Q>::default();
+    let tract = Cw721Contract::<T, C, E, Q>::default();
+    let tokens = tract18
+        .tokens
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // upstream counts existing tokens, we count minted ones to assign the
+    // next token id, so continue after the highest numeric id
+    let token_count = tokens
+        .iter()
+        .map(|(token_id, _)| token_number(token_id.as_bytes()))
+        .filter(|number| *number != u64::MAX)
+        .map(|number| number + 1)
+        .fold(tokens.len() as u64, u64::max);
+    if msg.collection_size < token_count {
+        return Err(ContractError::InvalidCollectionSize {});
+    }
+    validate_collection_size(msg.collection_size)?;
+    validate_base_token_uri(&msg.base_token_uri)?;
+    for (token_id, token) in tokens.iter() {
+        let expected = token_uri(&msg.base_token_uri, token_id);
+        if token
+            .token_uri
+            .as_ref()
+            .map_or(false, |uri| *uri != expected)
+        {
+            return Err(ContractError::TokenUriMismatch {
+                token_id: token_id.clone(),
+                expected,
+            });
+        }
+    }
+    let sale_config = SaleConfigResponse {
+        og_price: msg.og_price,
+        allowlist_price: msg.allowlist_price,
+        public_price: msg.public_price,
+        max_per_og: msg.max_per_og,
+        max_per_allowlist: msg.max_per_allowlist,
+        max_per_public: msg.max_per_public,
+        og_sale_open: false,
+        allowlist_sale_open: false,
+        public_sale_open: false,
+    };
+    validate_sale_config(&sale_config)?;
+
+    for (token_id, token) in tokens.iter() {
+        tract18.tokens.remove(deps.storage, token_id)?;
+        let token = TokenInfo {
+            owner: token.owner.clone(),
+            approvals: token
+                .approvals
+                .iter()
+                .map(|approval| Approval {
+                    spender: approval.spender.clone(),
+                    expires: approval.expires,
+                })
+                .collect(),
+            extension: token.extension.clone(),
+        };
+        tract.tokens.save(deps.storage, token_id, &token)?;
+        tract.increment_balance(deps.storage, &token.owner, 1)?;
+    }
+    tract.token_count.save(deps.storage, &token_count)?;
+
+    tract
+        .collection_size
+        .save(deps.storage, &msg.collection_size)?;
+    tract
+        .base_token_uri
+        .save(deps.storage, &msg.base_token_uri)?;
+    tract.sale_config.save(deps.storage, &sale_config)?;
+    let withdraw_address = match msg.withdraw_address {
+        Some(address) => deps.api.addr_validate(&address)?.into_string(),
+        None => match tract.withdraw_address.may_load(deps.storage)? {
+            Some(address) => address,
+            None => cw_ownable::get_ownership(deps.storage)?
+                .owner
+                .ok_or(ContractError::NoWithdrawAddress {})?
+                .into_string(),
+        },
+    };
+    tract
+        .withdraw_address
+        .save(deps.storage, &withdraw_address)?;
+
+    cw2::set_contract_version(deps.storage, contract, to)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from.to_string())
+        .add_attribute("to_version", to)
+        .add_attribute("migrated_tokens", tokens.len().to_string())
+        .add_attribute("token_count", token_count.to_string()))
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::{CustomMsg, DepsMut, Order, Response, StdResult};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{ContractError, Cw721Contract};
+
+/// Re-saves every token so the numeric token indexes are populated for
+/// tokens minted before they existed, and recounts owner balances.
+pub fn migrate<T, C, E, Q>(deps: DepsMut) -> Result<Response<C>, ContractError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    Q: CustomMsg,
+    E: CustomMsg,
+{
+    let tract = Cw721Contract::<T, C, E, Q>::default();
+    let tokens = tract
+        .tokens
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    tract.balances.clear(deps.storage);
+    tract.num_holders.remove(deps.storage);
+    for (token_id, token) in &tokens {
+        tract.tokens.save(deps.storage, token_id, token)?;
+        tract.increment_balance(deps.storage, &token.owner, 1)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("reindexed_tokens", tokens.len().to_string())
+        .add_attribute("num_holders", tract.num_holders(deps.storage)?.to_string()))
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{Addr, CustomMsg, DepsMut, Order, Response, StdResult, Storage};
+use cw_storage_plus::Map;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{ContractError, Cw721Contract};
+
+/// Turns the listed og and allowlist addresses into allocations of the
+/// current per-phase maximum.
+pub fn migrate<T, C, E, Q>(deps: DepsMut) -> Result<Response<C>, ContractError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    Q: CustomMsg,
+    E: CustomMsg,
+{
+    let tract = Cw721Contract::<T, C, E, Q>::default();
+    let sale_config = tract.sale_config.load(deps.storage)?;
+    let og = allocate(
+        deps.storage,
+        "og_list_key",
+        &tract.og_list,
+        sale_config.max_per_og,
+    )?;
+    let allowlist = allocate(
+        deps.storage,
+        "allowlist",
+        &tract.allowlist,
+        sale_config.max_per_allowlist,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("og_allocations", og.to_string())
+        .add_attribute("allowlist_allocations", allowlist.to_string()))
+}
+
+fn allocate(
+    storage: &mut dyn Storage,
+    key: &str,
+    list: &Map<&Addr, u64>,
+    allocation: u64,
+) -> StdResult<usize> {
+    let legacy: Map<&Addr, bool> = Map::new(key);
+    let listed = legacy
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (addr, _) in &listed {
+        list.save(storage, addr, &allocation)?;
+    }
+    Ok(listed.len())
+}
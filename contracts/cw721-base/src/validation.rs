@@ -0,0 +1,50 @@
+//! Checks on collection and sale parameters, run at instantiate and whenever
+//! the owner changes them.
+
+use crate::error::ContractError;
+use crate::msg::SaleConfigResponse;
+
+/// Schemes a base token uri can use. Token uris are built as
+/// `{base_token_uri}/{token_id}`.
+pub const BASE_TOKEN_URI_SCHEMES: [&str; 3] = ["ipfs://", "ar://", "https://"];
+
+pub fn validate_collection_size(collection_size: u64) -> Result<(), ContractError> {
+    if collection_size == 0 {
+        return Err(ContractError::InvalidCollectionSize {});
+    }
+    Ok(())
+}
+
+/// Every phase needs a positive per mint limit, prices may be zero for free mints
+pub fn validate_sale_config(config: &SaleConfigResponse) -> Result<(), ContractError> {
+    for (phase, max) in [
+        ("og", config.max_per_og),
+        ("allowlist", config.max_per_allowlist),
+        ("public", config.max_per_public),
+    ] {
+        if max == 0 {
+            return Err(ContractError::ZeroMintLimit {
+                phase: phase.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_base_token_uri(base_token_uri: &str) -> Result<(), ContractError> {
+    let path = BASE_TOKEN_URI_SCHEMES
+        .iter()
+        .find_map(|scheme| base_token_uri.strip_prefix(scheme));
+    let valid = match path {
+        Some(path) => {
+            !path.is_empty() && !path.ends_with('/') && !path.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(ContractError::InvalidBaseTokenUri {
+            base_token_uri: base_token_uri.to_string(),
+        });
+    }
+    Ok(())
+}